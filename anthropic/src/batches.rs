@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    completion::message::{MessageRequest, MessageResponse},
+    error::{AnthropicError, ApiErrorResponse},
+};
+
+/// A request to process asynchronously as part of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRequest {
+    /// Developer-provided ID used to match the results to the requests, as results can be returned in any order.
+    pub custom_id: String,
+
+    /// Parameters of the message to create.
+    pub params: MessageRequest,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBatch {
+    /// List of requests for prompt completion. Each is an individual request to create a message.
+    pub requests: Vec<BatchRequest>,
+}
+
+impl CreateBatch {
+    pub fn new(requests: Vec<(String, MessageRequest)>) -> Self {
+        Self {
+            requests: requests
+                .into_iter()
+                .map(|(custom_id, params)| BatchRequest { custom_id, params })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MessageBatch {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub batch_type: String,
+
+    /// Processing status of the batch.
+    pub processing_status: ProcessingStatus,
+
+    /// Number of requests in the batch by status.
+    pub request_counts: RequestCounts,
+
+    /// RFC 3339 datetime string representing the time at which processing for the batch ended.
+    pub ended_at: Option<String>,
+
+    /// RFC 3339 datetime string representing the time at which the batch was created.
+    pub created_at: String,
+
+    /// RFC 3339 datetime string representing the time at which the batch will expire and end processing, 24 hours after creation.
+    pub expires_at: String,
+
+    /// RFC 3339 datetime string representing the time at which the batch was archived and its results became unavailable.
+    pub archived_at: Option<String>,
+
+    /// RFC 3339 datetime string representing the time at which cancellation was initiated for the batch.
+    pub cancel_initiated_at: Option<String>,
+
+    /// URL to a `.jsonl` file containing the results of the requests. Only set once processing ended.
+    pub results_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStatus {
+    InProgress,
+    Canceling,
+    Ended,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RequestCounts {
+    pub processing: u32,
+    pub succeeded: u32,
+    pub errored: u32,
+    pub canceled: u32,
+    pub expired: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListBatches {
+    pub data: Vec<MessageBatch>,
+
+    /// Whether there are more results in the requested page direction.
+    pub has_more: bool,
+
+    /// First ID in the data list. Can be used as the **before_id** of the previous page.
+    pub first_id: Option<String>,
+
+    /// Last ID in the data list. Can be used as the **after_id** of the next page.
+    pub last_id: Option<String>,
+}
+
+/// Pagination parameters of [`Client::list_batches`](crate::client::Client::list_batches).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListBatchesParams {
+    /// Return the page of results immediately before this batch ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+
+    /// Return the page of results immediately after this batch ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+
+    /// Number of batches per page.
+    ///
+    /// Defaults to 20. Ranges from 1 to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl ListBatchesParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    pub fn with_after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeletedBatch {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub batch_type: String,
+}
+
+/// Result of a single request of a batch.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+pub struct BatchResult {
+    /// The ID of the request the result belongs to.
+    pub custom_id: String,
+
+    pub result: BatchOutcome,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchOutcome {
+    /// The request succeeded and created a message.
+    Succeeded { message: MessageResponse },
+
+    /// The request failed. Requests with an invalid payload are not billed.
+    Errored { error: ApiErrorResponse },
+
+    /// The batch was canceled before the request was processed.
+    Canceled,
+
+    /// The batch expired before the request was processed.
+    Expired,
+}
+
+/// Incremental parser turning the bytes of a JSONL results file into batch results.
+///
+/// Lines can be split across several network chunks so incomplete lines are buffered until their newline is received.
+#[derive(Debug, Default)]
+pub(crate) struct ResultParser {
+    buffer: Vec<u8>,
+}
+
+impl ResultParser {
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<Result<BatchResult, AnthropicError>> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut results = Vec::new();
+        while let Some(position) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..position + 1).collect();
+            results.extend(Self::parse_line(&line));
+        }
+        results
+    }
+
+    /// Parse the last line when the file doesn't end with a newline.
+    pub(crate) fn finish(&mut self) -> Vec<Result<BatchResult, AnthropicError>> {
+        let line = std::mem::take(&mut self.buffer);
+        Self::parse_line(&line).into_iter().collect()
+    }
+
+    fn parse_line(line: &[u8]) -> Option<Result<BatchResult, AnthropicError>> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        Some(serde_json::from_str::<BatchResult>(line).map_err(AnthropicError::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::claude::ClaudeModel;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_serialize_create_batch() {
+        let request = MessageRequest::new(ClaudeModel::Claude3Haiku, 1024, Vec::new());
+        let batch = CreateBatch::new(vec![("request-1".to_string(), request)]);
+
+        let value = serde_json::to_value(&batch).unwrap();
+        assert_eq!(value["requests"][0]["custom_id"], "request-1");
+        assert_eq!(
+            value["requests"][0]["params"]["model"],
+            "claude-3-haiku-20240307"
+        );
+    }
+
+    #[test]
+    fn should_parse_results_split_across_chunks() {
+        let succeeded = r#"{"custom_id":"request-1","result":{"type":"succeeded","message":{"id":"msg_014VwiXbi91y3JMjcpyGBHX5","type":"message","role":"assistant","model":"claude-3-haiku-20240307","content":[{"type":"text","text":"Hello"}],"stop_reason":"end_turn","stop_sequence":null,"usage":{"input_tokens":10,"output_tokens":1}}}}"#;
+        let errored = r#"{"custom_id":"request-2","result":{"type":"errored","error":{"type":"error","error":{"type":"invalid_request_error","message":"max_tokens: Field required"}}}}"#;
+        let expired = r#"{"custom_id":"request-3","result":{"type":"expired"}}"#;
+        let raw = format!("{}\n{}\n{}", succeeded, errored, expired);
+        let (first, second) = raw.as_bytes().split_at(100);
+
+        let mut parser = ResultParser::default();
+        assert!(parser.push(first).is_empty());
+        let mut results: Vec<BatchResult> = parser
+            .push(second)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        results.extend(parser.finish().into_iter().map(Result::unwrap));
+
+        assert_eq!(results.len(), 3);
+        match &results[0].result {
+            BatchOutcome::Succeeded { message } => {
+                assert_eq!(message.content[0].as_text(), Some("Hello"))
+            }
+            other => panic!("Expected 'Succeeded' outcome, got {:?}", other),
+        }
+        match &results[1].result {
+            BatchOutcome::Errored { error } => {
+                assert_eq!(error.error.message, "max_tokens: Field required")
+            }
+            other => panic!("Expected 'Errored' outcome, got {:?}", other),
+        }
+        assert_eq!(results[2].custom_id, "request-3");
+        assert_eq!(results[2].result, BatchOutcome::Expired);
+    }
+}
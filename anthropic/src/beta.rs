@@ -0,0 +1,129 @@
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{convert::Infallible, str::FromStr};
+
+/// A beta feature enabled with the **anthropic-beta** header.
+///
+/// Betas unknown to this version of the crate can be enabled with [`AnthropicBeta::Custom`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AnthropicBeta {
+    /// Up to 128k output tokens with Claude 3.7 Sonnet.
+    Output128k,
+    /// Thinking between tool calls with Claude 4 models.
+    InterleavedThinking,
+    /// The code execution server tool.
+    CodeExecution,
+    /// The Files API and **file** sources of documents and images.
+    FilesApi,
+    /// Search result content blocks.
+    SearchResults,
+    /// Any other beta, sent as is.
+    Custom(String),
+}
+
+const KNOWN_BETAS: &[AnthropicBeta] = &[
+    AnthropicBeta::Output128k,
+    AnthropicBeta::InterleavedThinking,
+    AnthropicBeta::CodeExecution,
+    AnthropicBeta::FilesApi,
+    AnthropicBeta::SearchResults,
+];
+
+impl AnthropicBeta {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Output128k => "output-128k-2025-02-19",
+            Self::InterleavedThinking => "interleaved-thinking-2025-05-14",
+            Self::CodeExecution => "code-execution-2025-05-22",
+            Self::FilesApi => "files-api-2025-04-14",
+            Self::SearchResults => "search-results-2025-06-09",
+            Self::Custom(beta) => beta,
+        }
+    }
+}
+
+impl FromStr for AnthropicBeta {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let beta = KNOWN_BETAS
+            .iter()
+            .find(|beta| beta.as_str() == s)
+            .cloned()
+            .unwrap_or_else(|| Self::Custom(s.to_string()));
+        Ok(beta)
+    }
+}
+
+impl fmt::Display for AnthropicBeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for AnthropicBeta {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for AnthropicBeta {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let beta = String::deserialize(deserializer)?;
+        Ok(beta.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+/// Add `beta` to `betas` unless already present.
+pub(crate) fn insert(betas: &mut Vec<AnthropicBeta>, beta: AnthropicBeta) {
+    if !betas.contains(&beta) {
+        betas.push(beta);
+    }
+}
+
+/// Value of the **anthropic-beta** header enabling each of the given betas once.
+pub(crate) fn header_value<'a>(
+    betas: impl IntoIterator<Item = &'a AnthropicBeta>,
+) -> Option<String> {
+    let mut unique = Vec::new();
+    for beta in betas {
+        insert(&mut unique, beta.clone());
+    }
+    if unique.is_empty() {
+        return None;
+    }
+    let values: Vec<&str> = unique.iter().map(AnthropicBeta::as_str).collect();
+    Some(values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_betas() {
+        assert_eq!(
+            AnthropicBeta::from_str("files-api-2025-04-14").unwrap(),
+            AnthropicBeta::FilesApi
+        );
+        assert_eq!(
+            AnthropicBeta::from_str("new-feature-2099-01-01").unwrap(),
+            AnthropicBeta::Custom("new-feature-2099-01-01".to_string())
+        );
+    }
+
+    #[test]
+    fn should_deduplicate_header_value() {
+        let betas = [
+            AnthropicBeta::FilesApi,
+            AnthropicBeta::CodeExecution,
+            AnthropicBeta::from_str("files-api-2025-04-14").unwrap(),
+        ];
+        assert_eq!(
+            header_value(&betas).as_deref(),
+            Some("files-api-2025-04-14,code-execution-2025-05-22")
+        );
+        assert_eq!(header_value(&[]), None);
+    }
+}
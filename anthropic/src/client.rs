@@ -1,31 +1,41 @@
 use core::fmt;
-use futures_util::{stream, Stream, StreamExt};
+use futures_util::{future, stream, Stream, StreamExt};
 use reqwest::{
     header::{HeaderMap, HeaderValue, ACCEPT, CONTENT_TYPE},
-    Method, RequestBuilder, Url,
+    multipart::{Form, Part},
+    Method, RequestBuilder, Response, Url,
 };
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{path::Path, str::FromStr};
 
 use crate::{
+    batches::{
+        BatchResult, CreateBatch, DeletedBatch, ListBatches, ListBatchesParams, MessageBatch,
+        ResultParser,
+    },
+    beta::{self, AnthropicBeta},
     completion::{
         message::{MessageRequest, MessageResponse},
-        stream::StreamEvent,
+        stream::{EventParser, StreamEvent},
+        tokens::{CountTokensRequest, TokenCount},
     },
     config::Config,
     error::{AnthropicError, ApiErrorResponse},
+    files::{DeletedFile, FileMetadata, ListFiles, ListFilesParams},
+    models::claude::{ListModels, ListModelsParams, ModelInfo},
 };
 
 const ANTHROPIC_API_KEY_HEADER: &str = "x-api-key";
 const ANTHROPIC_BETA_HEADERS: &str = "anthropic-beta";
 const ANTHROPIC_VERSION_HEADER: &str = "anthropic-version";
+const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
 
 pub struct Client {
     api_key: String,
     api_version: ApiVersion,
     anthropic_version: AnthropicVersion,
     base_url: Url,
-    beta_headers: Option<String>,
+    betas: Vec<AnthropicBeta>,
     http_client: reqwest::Client,
 }
 
@@ -58,7 +68,7 @@ impl Client {
             api_key: config.api_key,
             api_version: config.api_version,
             base_url,
-            beta_headers: None,
+            betas: Vec::new(),
             http_client,
         })
     }
@@ -79,127 +89,329 @@ impl Client {
         self.base_url.as_str()
     }
 
-    pub fn beta_headers(&self) -> Option<&str> {
-        self.beta_headers.as_deref()
+    /// Betas enabled for every request of the client.
+    pub fn betas(&self) -> &[AnthropicBeta] {
+        &self.betas
     }
 
-    pub fn with_beta_header(&mut self, new_header: &str) {
-        if let Some(existing_headers) = &self.beta_headers {
-            self.beta_headers = Some(format!("{},{}", existing_headers, new_header));
-        } else {
-            self.beta_headers = Some(new_header.to_string());
-        }
+    /// Enable `beta` for every request of the client. Betas already enabled are ignored.
+    pub fn with_beta(mut self, beta: AnthropicBeta) -> Self {
+        beta::insert(&mut self.betas, beta);
+        self
     }
 
-    pub fn with_beta_headers(&mut self, headers: &[&str]) {
-        if let Some(existing_headers) = &self.beta_headers {
-            let new_headers = headers.join(",");
-            self.beta_headers = Some(format!("{},{}", existing_headers, new_headers));
-        } else {
-            self.beta_headers = Some(headers.join(","));
+    pub fn with_betas(mut self, betas: impl IntoIterator<Item = AnthropicBeta>) -> Self {
+        for beta in betas {
+            beta::insert(&mut self.betas, beta);
         }
+        self
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, AnthropicError> {
+        self.request_with_betas(method, path, &[])
+    }
+
+    /// Build a request enabling the betas of the client along with `betas`.
+    fn request_with_betas(
+        &self,
+        method: Method,
+        path: &str,
+        betas: &[AnthropicBeta],
+    ) -> Result<RequestBuilder, AnthropicError> {
         let url = self
             .base_url
             .join(path)
             .map_err(|err| AnthropicError::UrlParse(err.to_string()))?;
         let req = self.http_client.request(method, url);
-        if let Some(beta_headers) = &self.beta_headers {
-            Ok(req.header(ANTHROPIC_BETA_HEADERS, beta_headers))
+        if let Some(betas) = beta::header_value(self.betas.iter().chain(betas)) {
+            Ok(req.header(ANTHROPIC_BETA_HEADERS, betas))
         } else {
             Ok(req)
         }
     }
 
+    /// Betas of `request`, after checking that the betas its fields require are enabled.
+    fn request_betas<'a>(
+        &self,
+        request: &'a MessageRequest,
+    ) -> Result<&'a [AnthropicBeta], AnthropicError> {
+        for (beta, feature) in request.required_betas() {
+            if !self.betas.contains(&beta) && !request.betas.contains(&beta) {
+                return Err(AnthropicError::BetaRequired { beta, feature });
+            }
+        }
+        Ok(&request.betas)
+    }
+
     pub async fn create_message(
         &self,
         payload: MessageRequest,
     ) -> Result<MessageResponse, AnthropicError> {
+        payload.validate_with_betas(&self.betas)?;
+        let betas = self.request_betas(&payload)?;
+
         let response = self
-            .request(Method::POST, "messages")?
+            .request_with_betas(Method::POST, "messages", betas)?
             .json(&payload)
             .send()
             .await?;
-
-        if !response.status().is_success() {
-            let error = response.text().await?;
-            match serde_json::from_str::<ApiErrorResponse>(&error) {
-                Ok(api_error) => return Err(AnthropicError::Api(api_error)),
-                Err(err) => return Err(AnthropicError::JsonDeserialize(err)),
-            }
-        }
-
-        response
-            .json::<MessageResponse>()
-            .await
-            .map_err(AnthropicError::from)
+        Self::handle_response::<MessageResponse>(response).await
     }
 
     pub async fn stream_message(
         &self,
         request: MessageRequest,
     ) -> Result<impl Stream<Item = Result<StreamEvent, AnthropicError>>, AnthropicError> {
+        request.validate_with_betas(&self.betas)?;
+        let betas = self.request_betas(&request)?;
+
         let response = self
-            .request(Method::POST, "messages")?
+            .request_with_betas(Method::POST, "messages", betas)?
             .header(ACCEPT, "text/event-stream")
             .json(&request)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let error = response.text().await?;
-            match serde_json::from_str::<ApiErrorResponse>(&error) {
-                Ok(api_error) => return Err(AnthropicError::Api(api_error)),
-                Err(err) => return Err(AnthropicError::JsonDeserialize(err)),
-            }
+            return Err(Self::handle_error(response).await);
         }
 
-        Ok(response.bytes_stream().flat_map(move |chunk| match chunk {
-            Ok(bytes) => {
-                let events = Self::parse_stream_chunk(&bytes);
-                stream::iter(events)
-            }
-            Err(err) => stream::iter(vec![Err(AnthropicError::from(err))]),
-        }))
+        Ok(response
+            .bytes_stream()
+            .map(Some)
+            .chain(stream::once(future::ready(None)))
+            .scan(EventParser::default(), |parser, chunk| {
+                let events = match chunk {
+                    Some(Ok(bytes)) => parser.push(&bytes),
+                    Some(Err(err)) => vec![Err(AnthropicError::from(err))],
+                    None => parser.finish(),
+                };
+                future::ready(Some(stream::iter(events)))
+            })
+            .flatten())
     }
 
-    fn parse_stream_chunk(bytes: &[u8]) -> Vec<Result<StreamEvent, AnthropicError>> {
-        let chunk_str = match std::str::from_utf8(bytes).map_err(AnthropicError::Utf8Error) {
-            Ok(chunk_str) => chunk_str,
-            Err(err) => return vec![Err(err)],
-        };
-        chunk_str
-            .split("\n\n")
-            .filter(|event| !event.trim().is_empty())
-            .map(|event| {
-                event
-                    .lines()
-                    .find(|line| line.starts_with("data: "))
-                    .and_then(|line| line.strip_prefix("data: "))
-                    .ok_or(AnthropicError::InvalidStreamEvent)
-                    .and_then(|content| {
-                        StreamEvent::from_str(content)
-                            .map_err(|_| AnthropicError::InvalidStreamEvent)
-                    })
+    /// Count the number of input tokens of a message, including the system prompt, without creating it.
+    pub async fn count_tokens(
+        &self,
+        request: &MessageRequest,
+    ) -> Result<TokenCount, AnthropicError> {
+        let betas = self.request_betas(request)?;
+        let response = self
+            .request_with_betas(Method::POST, "messages/count_tokens", betas)?
+            .json(&CountTokensRequest::from(request))
+            .send()
+            .await?;
+        Self::handle_response::<TokenCount>(response).await
+    }
+
+    /// List the available models, most recently released first.
+    pub async fn list_models(
+        &self,
+        params: &ListModelsParams,
+    ) -> Result<ListModels, AnthropicError> {
+        let response = self
+            .request(Method::GET, "models")?
+            .query(params)
+            .send()
+            .await?;
+        Self::handle_response::<ListModels>(response).await
+    }
+
+    /// Get a model by ID or alias e.g. **claude-sonnet-4-0**.
+    pub async fn get_model(&self, model_id: &str) -> Result<ModelInfo, AnthropicError> {
+        let path = format!("models/{}", model_id);
+        let response = self.request(Method::GET, &path)?.send().await?;
+        Self::handle_response::<ModelInfo>(response).await
+    }
+
+    /// Create a batch of messages, processed asynchronously at a lower cost.
+    pub async fn create_batch(&self, payload: CreateBatch) -> Result<MessageBatch, AnthropicError> {
+        let mut betas = Vec::new();
+        for request in &payload.requests {
+            request.params.validate_with_betas(&self.betas)?;
+            betas.extend_from_slice(self.request_betas(&request.params)?);
+        }
+
+        let response = self
+            .request_with_betas(Method::POST, "messages/batches", &betas)?
+            .json(&payload)
+            .send()
+            .await?;
+        Self::handle_response::<MessageBatch>(response).await
+    }
+
+    /// Get a batch. Poll it until its processing status is **ended** to fetch its results.
+    pub async fn batch(&self, batch_id: &str) -> Result<MessageBatch, AnthropicError> {
+        let path = format!("messages/batches/{}", batch_id);
+        let response = self.request(Method::GET, &path)?.send().await?;
+        Self::handle_response::<MessageBatch>(response).await
+    }
+
+    /// List batches, most recently created first.
+    pub async fn list_batches(
+        &self,
+        params: &ListBatchesParams,
+    ) -> Result<ListBatches, AnthropicError> {
+        let response = self
+            .request(Method::GET, "messages/batches")?
+            .query(params)
+            .send()
+            .await?;
+        Self::handle_response::<ListBatches>(response).await
+    }
+
+    /// Cancel a batch. Requests already being processed are completed.
+    pub async fn cancel_batch(&self, batch_id: &str) -> Result<MessageBatch, AnthropicError> {
+        let path = format!("messages/batches/{}/cancel", batch_id);
+        let response = self.request(Method::POST, &path)?.send().await?;
+        Self::handle_response::<MessageBatch>(response).await
+    }
+
+    /// Delete a batch. Batches must have ended processing before being deleted.
+    pub async fn delete_batch(&self, batch_id: &str) -> Result<DeletedBatch, AnthropicError> {
+        let path = format!("messages/batches/{}", batch_id);
+        let response = self.request(Method::DELETE, &path)?.send().await?;
+        Self::handle_response::<DeletedBatch>(response).await
+    }
+
+    /// Stream the results of an ended batch, one per request.
+    ///
+    /// Results are not returned in the order of the requests, use their **custom_id** to match them.
+    pub async fn batch_results(
+        &self,
+        batch_id: &str,
+    ) -> Result<impl Stream<Item = Result<BatchResult, AnthropicError>>, AnthropicError> {
+        let path = format!("messages/batches/{}/results", batch_id);
+        let response = self.request(Method::GET, &path)?.send().await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error(response).await);
+        }
+
+        Ok(response
+            .bytes_stream()
+            .map(Some)
+            .chain(stream::once(future::ready(None)))
+            .scan(ResultParser::default(), |parser, chunk| {
+                let results = match chunk {
+                    Some(Ok(bytes)) => parser.push(&bytes),
+                    Some(Err(err)) => vec![Err(AnthropicError::from(err))],
+                    None => parser.finish(),
+                };
+                future::ready(Some(stream::iter(results)))
             })
-            .collect()
+            .flatten())
+    }
+
+    /// Upload the file at `path`, guessing its MIME type from its extension.
+    pub async fn upload_file(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<FileMetadata, AnthropicError> {
+        let path = path.as_ref();
+        let data = tokio::fs::read(path).await?;
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mime_type = mime_guess::from_path(path)
+            .first_raw()
+            .unwrap_or(DEFAULT_MIME_TYPE);
+        self.upload_bytes(data, filename, mime_type).await
+    }
+
+    /// Upload in-memory content as a file named `filename`.
+    pub async fn upload_bytes(
+        &self,
+        data: impl Into<Vec<u8>>,
+        filename: impl Into<String>,
+        mime_type: &str,
+    ) -> Result<FileMetadata, AnthropicError> {
+        let part = Part::bytes(data.into())
+            .file_name(filename.into())
+            .mime_str(mime_type)?;
+        let response = self
+            .request_with_betas(Method::POST, "files", &[AnthropicBeta::FilesApi])?
+            .multipart(Form::new().part("file", part))
+            .send()
+            .await?;
+        Self::handle_response::<FileMetadata>(response).await
+    }
+
+    /// List uploaded files, most recently created first.
+    pub async fn list_files(&self, params: &ListFilesParams) -> Result<ListFiles, AnthropicError> {
+        let response = self
+            .request_with_betas(Method::GET, "files", &[AnthropicBeta::FilesApi])?
+            .query(params)
+            .send()
+            .await?;
+        Self::handle_response::<ListFiles>(response).await
+    }
+
+    pub async fn file(&self, file_id: &str) -> Result<FileMetadata, AnthropicError> {
+        let path = format!("files/{}", file_id);
+        let response = self
+            .request_with_betas(Method::GET, &path, &[AnthropicBeta::FilesApi])?
+            .send()
+            .await?;
+        Self::handle_response::<FileMetadata>(response).await
+    }
+
+    /// Download the content of a file. Only files created by tools are downloadable.
+    pub async fn download_file(&self, file_id: &str) -> Result<Vec<u8>, AnthropicError> {
+        let path = format!("files/{}/content", file_id);
+        let response = self
+            .request_with_betas(Method::GET, &path, &[AnthropicBeta::FilesApi])?
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error(response).await);
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    pub async fn delete_file(&self, file_id: &str) -> Result<DeletedFile, AnthropicError> {
+        let path = format!("files/{}", file_id);
+        let response = self
+            .request_with_betas(Method::DELETE, &path, &[AnthropicBeta::FilesApi])?
+            .send()
+            .await?;
+        Self::handle_response::<DeletedFile>(response).await
+    }
+
+    async fn handle_response<T>(response: Response) -> Result<T, AnthropicError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        if !response.status().is_success() {
+            return Err(Self::handle_error(response).await);
+        }
+        let body = response.text().await?;
+        serde_json::from_str::<T>(&body).map_err(AnthropicError::from)
+    }
+
+    /// Turn an unsuccessful response into an API error.
+    async fn handle_error(response: Response) -> AnthropicError {
+        let body = match response.text().await {
+            Ok(body) => body,
+            Err(err) => return AnthropicError::from(err),
+        };
+        match serde_json::from_str::<ApiErrorResponse>(&body) {
+            Ok(api_error) => AnthropicError::Api(api_error),
+            Err(err) => AnthropicError::JsonDeserialize(err),
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum AnthropicVersion {
+    #[default]
     Latest,
     Initial,
 }
 
-impl Default for AnthropicVersion {
-    fn default() -> Self {
-        Self::Latest
-    }
-}
-
 impl fmt::Display for AnthropicVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -209,17 +421,12 @@ impl fmt::Display for AnthropicVersion {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum ApiVersion {
+    #[default]
     V1,
 }
 
-impl Default for ApiVersion {
-    fn default() -> Self {
-        Self::V1
-    }
-}
-
 impl fmt::Display for ApiVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -242,3 +449,39 @@ impl FromStr for ApiVersion {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{completion::tool::CodeExecutionTool, models::claude::ClaudeModel};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_require_betas_of_request_fields() {
+        let client = Client::new(Config::new("api-key"))
+            .unwrap()
+            .with_beta(AnthropicBeta::FilesApi);
+        let request = MessageRequest::new(ClaudeModel::ClaudeSonnet4, 1024, Vec::new())
+            .with_tool(CodeExecutionTool::new());
+
+        assert!(matches!(
+            client.request_betas(&request),
+            Err(AnthropicError::BetaRequired {
+                beta: AnthropicBeta::CodeExecution,
+                ..
+            })
+        ));
+
+        let request = request.with_beta(AnthropicBeta::CodeExecution);
+        let betas = client.request_betas(&request).unwrap();
+        let built = client
+            .request_with_betas(Method::POST, "messages", betas)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            built.headers()[ANTHROPIC_BETA_HEADERS],
+            "files-api-2025-04-14,code-execution-2025-05-22"
+        );
+    }
+}
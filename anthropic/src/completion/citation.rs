@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+/// Whether the model cites the passages of a document it uses in its answer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CitationsConfig {
+    pub enabled: bool,
+}
+
+/// A passage of a source supporting a text block of the response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Citation {
+    /// A range of characters of a plain text document.
+    CharLocation {
+        cited_text: String,
+        document_index: u32,
+        document_title: Option<String>,
+        start_char_index: u32,
+        /// Exclusive end of the range.
+        end_char_index: u32,
+    },
+
+    /// A range of pages of a PDF document, numbered from 1.
+    PageLocation {
+        cited_text: String,
+        document_index: u32,
+        document_title: Option<String>,
+        start_page_number: u32,
+        /// Exclusive end of the range.
+        end_page_number: u32,
+    },
+
+    /// A range of content blocks of a custom content document.
+    ContentBlockLocation {
+        cited_text: String,
+        document_index: u32,
+        document_title: Option<String>,
+        start_block_index: u32,
+        /// Exclusive end of the range.
+        end_block_index: u32,
+    },
+
+    /// A range of content blocks of a search result.
+    SearchResultLocation {
+        cited_text: String,
+        search_result_index: u32,
+        source: String,
+        title: Option<String>,
+        start_block_index: u32,
+        /// Exclusive end of the range.
+        end_block_index: u32,
+    },
+
+    /// A web page returned by the web search tool.
+    WebSearchResultLocation {
+        cited_text: String,
+        url: String,
+        title: Option<String>,
+        /// Reference to the cited passage, to send back unchanged in following turns.
+        encrypted_index: String,
+    },
+}
+
+impl Citation {
+    /// The text of the source quoted by the citation.
+    pub fn cited_text(&self) -> &str {
+        match self {
+            Self::CharLocation { cited_text, .. }
+            | Self::PageLocation { cited_text, .. }
+            | Self::ContentBlockLocation { cited_text, .. }
+            | Self::SearchResultLocation { cited_text, .. }
+            | Self::WebSearchResultLocation { cited_text, .. } => cited_text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_deserialize_citations() {
+        let json = serde_json::json!([
+            {
+                "type": "char_location",
+                "cited_text": "The grass is green.",
+                "document_index": 0,
+                "document_title": "Facts",
+                "start_char_index": 0,
+                "end_char_index": 20
+            },
+            {
+                "type": "page_location",
+                "cited_text": "The sky is blue.",
+                "document_index": 1,
+                "document_title": null,
+                "start_page_number": 2,
+                "end_page_number": 3
+            }
+        ]);
+        let citations: Vec<Citation> = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            citations[0],
+            Citation::CharLocation {
+                cited_text: "The grass is green.".to_string(),
+                document_index: 0,
+                document_title: Some("Facts".to_string()),
+                start_char_index: 0,
+                end_char_index: 20,
+            }
+        );
+        assert_eq!(citations[1].cited_text(), "The sky is blue.");
+    }
+}
@@ -2,7 +2,18 @@ use core::fmt;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-use crate::models::claude::ClaudeModel;
+use crate::{
+    beta::{self, AnthropicBeta},
+    completion::{
+        citation::{Citation, CitationsConfig},
+        tool::ToolDefinition,
+    },
+    error::ValidationError,
+    models::claude::ClaudeModel,
+};
+
+/// Smallest thinking budget accepted by the API.
+const MIN_THINKING_BUDGET: u32 = 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Message {
@@ -17,11 +28,487 @@ pub enum Role {
     Assistant,
 }
 
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User => write!(f, "user"),
+            Self::Assistant => write!(f, "assistant"),
+        }
+    }
+}
+
+/// A block of content of a message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Content {
+    Text(TextBlock),
+    Image(ImageBlock),
+    ToolUse(ToolUseBlock),
+    ToolResult(ToolResultBlock),
+    Thinking(ThinkingBlock),
+    RedactedThinking(RedactedThinkingBlock),
+    Document(DocumentBlock),
+    SearchResult(SearchResultBlock),
+
+    /// A call to a server tool, run by the API.
+    ServerToolUse(ToolUseBlock),
+    WebSearchToolResult(WebSearchToolResultBlock),
+    CodeExecutionToolResult(CodeExecutionToolResultBlock),
+}
+
+impl Content {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(TextBlock {
+            text: text.into(),
+            citations: None,
+            cache_control: None,
+        })
+    }
+
+    /// An image encoded in base64 e.g. with the **image/png** media type.
+    pub fn image_base64(media_type: impl Into<String>, data: impl Into<String>) -> Self {
+        Self::Image(ImageBlock {
+            source: ImageSource::Base64 {
+                media_type: media_type.into(),
+                data: data.into(),
+            },
+            cache_control: None,
+        })
+    }
+
+    pub fn image_url(url: impl Into<String>) -> Self {
+        Self::Image(ImageBlock {
+            source: ImageSource::Url { url: url.into() },
+            cache_control: None,
+        })
+    }
+
+    /// An image uploaded with [`Client::upload_file`](crate::client::Client::upload_file), sent by ID instead of its content.
+    pub fn image_file(file_id: impl Into<String>) -> Self {
+        Self::Image(ImageBlock {
+            source: ImageSource::File {
+                file_id: file_id.into(),
+            },
+            cache_control: None,
+        })
+    }
+
+    pub fn tool_use(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        input: serde_json::Value,
+    ) -> Self {
+        Self::ToolUse(ToolUseBlock {
+            id: id.into(),
+            name: name.into(),
+            input,
+            cache_control: None,
+        })
+    }
+
+    /// The result of the tool call **tool_use_id**, sent back in a user message.
+    pub fn tool_result(tool_use_id: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::ToolResult(ToolResultBlock {
+            tool_use_id: tool_use_id.into(),
+            content: Some(ToolResultContent::Text(content.into())),
+            is_error: None,
+            cache_control: None,
+        })
+    }
+
+    /// Mark the block as a cache breakpoint: the prompt up to and including this block is cached.
+    ///
+    /// Thinking blocks can't be breakpoints and are left unchanged.
+    pub fn with_cache_control(mut self, cache_control: CacheControl) -> Self {
+        if let Some(current) = self.cache_control_mut() {
+            *current = Some(cache_control);
+        }
+        self
+    }
+
+    pub fn cache_control(&self) -> Option<&CacheControl> {
+        match self {
+            Self::Text(block) => block.cache_control.as_ref(),
+            Self::Image(block) => block.cache_control.as_ref(),
+            Self::ToolUse(block) => block.cache_control.as_ref(),
+            Self::ToolResult(block) => block.cache_control.as_ref(),
+            Self::Document(block) => block.cache_control.as_ref(),
+            Self::SearchResult(block) => block.cache_control.as_ref(),
+            Self::ServerToolUse(block) => block.cache_control.as_ref(),
+            Self::WebSearchToolResult(block) => block.cache_control.as_ref(),
+            Self::CodeExecutionToolResult(block) => block.cache_control.as_ref(),
+            Self::Thinking(_) | Self::RedactedThinking(_) => None,
+        }
+    }
+
+    /// Whether the block, or one of the blocks it contains, matches `predicate`.
+    fn any_block(&self, predicate: &impl Fn(&Content) -> bool) -> bool {
+        if predicate(self) {
+            return true;
+        }
+        let nested = match self {
+            Self::ToolResult(ToolResultBlock {
+                content: Some(ToolResultContent::Blocks(blocks)),
+                ..
+            }) => blocks.as_slice(),
+            Self::Document(DocumentBlock {
+                source: DocumentSource::Content { content },
+                ..
+            }) => content.as_slice(),
+            Self::SearchResult(block) => block.content.as_slice(),
+            _ => &[],
+        };
+        nested.iter().any(|block| block.any_block(predicate))
+    }
+
+    fn has_file_source(&self) -> bool {
+        matches!(
+            self,
+            Self::Image(ImageBlock {
+                source: ImageSource::File { .. },
+                ..
+            }) | Self::Document(DocumentBlock {
+                source: DocumentSource::File { .. },
+                ..
+            })
+        )
+    }
+
+    /// The text of the block, if it is a text block.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(block) => Some(&block.text),
+            _ => None,
+        }
+    }
+
+    fn cache_control_mut(&mut self) -> Option<&mut Option<CacheControl>> {
+        match self {
+            Self::Text(block) => Some(&mut block.cache_control),
+            Self::Image(block) => Some(&mut block.cache_control),
+            Self::ToolUse(block) => Some(&mut block.cache_control),
+            Self::ToolResult(block) => Some(&mut block.cache_control),
+            Self::Document(block) => Some(&mut block.cache_control),
+            Self::SearchResult(block) => Some(&mut block.cache_control),
+            Self::ServerToolUse(block) => Some(&mut block.cache_control),
+            Self::WebSearchToolResult(block) => Some(&mut block.cache_control),
+            Self::CodeExecutionToolResult(block) => Some(&mut block.cache_control),
+            Self::Thinking(_) | Self::RedactedThinking(_) => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Content {
+pub struct TextBlock {
     pub text: String,
+
+    /// Passages of the documents supporting the text, when citations are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citations: Option<Vec<Citation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+/// A document the model can read and cite.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DocumentBlock {
+    pub source: DocumentSource,
+
+    /// Title of the document, passed to the model and returned in citations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    /// Context about the document passed to the model but never cited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citations: Option<CitationsConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl DocumentBlock {
+    pub fn new(source: DocumentSource) -> Self {
+        Self {
+            source,
+            title: None,
+            context: None,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    /// A plain text document, cited by character ranges.
+    pub fn text(data: impl Into<String>) -> Self {
+        Self::new(DocumentSource::Text {
+            media_type: "text/plain".to_string(),
+            data: data.into(),
+        })
+    }
+
+    /// A PDF document encoded in base64, cited by page ranges.
+    pub fn pdf_base64(data: impl Into<String>) -> Self {
+        Self::new(DocumentSource::Base64 {
+            media_type: "application/pdf".to_string(),
+            data: data.into(),
+        })
+    }
+
+    /// A PDF document fetched by the API from a URL, cited by page ranges.
+    pub fn pdf_url(url: impl Into<String>) -> Self {
+        Self::new(DocumentSource::Url { url: url.into() })
+    }
+
+    /// A document uploaded with [`Client::upload_file`](crate::client::Client::upload_file), sent by ID instead of its content.
+    pub fn file(file_id: impl Into<String>) -> Self {
+        Self::new(DocumentSource::File {
+            file_id: file_id.into(),
+        })
+    }
+
+    /// A document made of custom content blocks, e.g. one per chunk of a retrieved passage, cited by block ranges.
+    pub fn content(content: Vec<Content>) -> Self {
+        Self::new(DocumentSource::Content { content })
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    pub fn with_citations(mut self, enabled: bool) -> Self {
+        self.citations = Some(CitationsConfig { enabled });
+        self
+    }
+}
+
+impl From<DocumentBlock> for Content {
+    fn from(block: DocumentBlock) -> Self {
+        Self::Document(block)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DocumentSource {
+    /// Plain text with the **text/plain** media type.
+    Text { media_type: String, data: String },
+
+    /// A PDF encoded in base64 with the **application/pdf** media type.
+    Base64 { media_type: String, data: String },
+
+    /// A PDF fetched by the API from a URL.
+    Url { url: String },
+
+    /// Text content blocks, each one being a citable unit.
+    Content { content: Vec<Content> },
+
+    /// A PDF or plain text file uploaded with the Files API. Requires the **files-api-2025-04-14** beta.
+    File { file_id: String },
+}
+
+/// A result of a search performed by the application, e.g. in a RAG tool result, that the model can cite.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SearchResultBlock {
+    /// URL or identifier of the source of the result.
+    pub source: String,
+
+    pub title: String,
+
+    /// Text content blocks of the result.
+    pub content: Vec<Content>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub citations: Option<CitationsConfig>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl SearchResultBlock {
+    pub fn new(source: impl Into<String>, title: impl Into<String>, content: Vec<Content>) -> Self {
+        Self {
+            source: source.into(),
+            title: title.into(),
+            content,
+            citations: None,
+            cache_control: None,
+        }
+    }
+
+    pub fn with_citations(mut self, enabled: bool) -> Self {
+        self.citations = Some(CitationsConfig { enabled });
+        self
+    }
+}
+
+impl From<SearchResultBlock> for Content {
+    fn from(block: SearchResultBlock) -> Self {
+        Self::SearchResult(block)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ImageBlock {
+    pub source: ImageSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    /// Image data encoded in base64.
+    ///
+    /// Supported media types are **image/jpeg**, **image/png**, **image/gif** and **image/webp**.
+    Base64 { media_type: String, data: String },
+
+    /// Image fetched by the API from a URL.
+    Url { url: String },
+
+    /// An image uploaded with the Files API. Requires the **files-api-2025-04-14** beta.
+    File { file_id: String },
+}
+
+/// A request from the model to call a tool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolUseBlock {
+    pub id: String,
+
+    /// Name of the tool to call.
+    pub name: String,
+
+    /// Input of the tool, matching its **input_schema**.
+    pub input: serde_json::Value,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolResultBlock {
+    /// The ID of the tool use block this is the result of.
+    pub tool_use_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<ToolResultContent>,
+
+    /// Whether the tool call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_error: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+/// The reasoning of the model before its answer, when extended thinking is enabled.
+///
+/// Thinking blocks must be sent back unchanged, signature included, along with the tool results of the same assistant turn.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThinkingBlock {
+    pub thinking: String,
+
+    /// Signature verifying the thinking was generated by the model. Empty in **content_block_start** stream events.
+    #[serde(default)]
+    pub signature: String,
+}
+
+/// A thinking block flagged by the safety systems, whose content is encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RedactedThinkingBlock {
+    pub data: String,
+}
+
+/// Results of a web search performed by the API.
+///
+/// Results must be sent back unchanged in following turns for their citations to stay valid.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebSearchToolResultBlock {
+    /// The ID of the server tool use block this is the result of.
+    pub tool_use_id: String,
+
+    pub content: WebSearchToolResultContent,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum WebSearchToolResultContent {
+    Results(Vec<WebSearchResult>),
+    Error(ServerToolError),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebSearchResult {
+    /// Always **web_search_result**.
     #[serde(rename = "type")]
-    pub content_type: ContentType,
+    pub result_type: String,
+
+    pub url: String,
+
+    pub title: String,
+
+    /// Content of the page, readable by the model only.
+    pub encrypted_content: String,
+
+    /// When the page was last updated e.g. **April 30, 2025**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_age: Option<String>,
+}
+
+/// An error of a server tool, e.g. **max_uses_exceeded** or **too_many_requests**.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServerToolError {
+    /// The type of the error e.g. **web_search_tool_result_error**.
+    #[serde(rename = "type")]
+    pub error_type: String,
+
+    pub error_code: String,
+}
+
+/// Output of code run by the code execution tool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodeExecutionToolResultBlock {
+    /// The ID of the server tool use block this is the result of.
+    pub tool_use_id: String,
+
+    pub content: CodeExecutionToolResultContent,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CodeExecutionToolResultContent {
+    CodeExecutionResult {
+        stdout: String,
+        stderr: String,
+        return_code: i32,
+        /// Files created by the code.
+        #[serde(default)]
+        content: Vec<serde_json::Value>,
+    },
+    CodeExecutionToolResultError {
+        error_code: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ToolResultContent {
+    Text(String),
+    Blocks(Vec<Content>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -52,6 +539,14 @@ pub enum CacheType {
     Ephemeral,
 }
 
+impl CacheControl {
+    pub fn ephemeral() -> Self {
+        Self {
+            cache_type: CacheType::Ephemeral,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MessageRequest {
     /// The model that will complete your prompt e.g. Claude 3.5 Sonnet
@@ -68,9 +563,11 @@ pub struct MessageRequest {
     pub messages: Vec<Message>,
 
     /// An object describing metadata about the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<MessageMetadata>,
 
     /// Custom text sequences that will cause the model to stop generating.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stop_sequences: Option<Vec<String>>,
 
     /// Whether to incrementally stream the response using server-sent events.
@@ -82,6 +579,18 @@ pub struct MessageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<System>,
 
+    /// Configuration of extended thinking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thinking: Option<Thinking>,
+
+    /// Betas enabled for this request, in addition to the betas of the client.
+    #[serde(skip)]
+    pub betas: Vec<AnthropicBeta>,
+
+    /// Definitions of the tools the model may use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolDefinition>>,
+
     /// Amount of randomness injected into the response.
     ///
     /// Defaults to 1.0. Ranges from 0.0 to 1.0.
@@ -95,13 +604,15 @@ pub struct MessageRequest {
     ///
     /// Used to remove "long tail" low probability responses. Learn more technical details here.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub top_k: Option<i8>,
+    pub top_k: Option<u32>,
 
     /// Use nucleus sampling.
     ///
     /// In nucleus sampling, we compute the cumulative distribution over all the options for each subsequent token in decreasing probability order and cut it off once it reaches a particular probability specified by top_p. You should either alter temperature or top_p, but not both.
+    ///
+    /// Ranges from 0.0 to 1.0.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub top_p: Option<i8>,
+    pub top_p: Option<f32>,
 }
 
 impl MessageRequest {
@@ -134,20 +645,171 @@ impl MessageRequest {
         self
     }
 
+    /// Enable extended thinking with a budget of `budget_tokens`.
+    ///
+    /// The budget counts towards **max_tokens** and must be at least 1024 tokens.
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.thinking = Some(Thinking::Enabled { budget_tokens });
+        self
+    }
+
+    /// Enable `beta` for this request only. Betas already enabled are ignored.
+    pub fn with_beta(mut self, beta: AnthropicBeta) -> Self {
+        beta::insert(&mut self.betas, beta);
+        self
+    }
+
+    /// Betas required by the fields of the request, along with the feature requiring them.
+    pub fn required_betas(&self) -> Vec<(AnthropicBeta, &'static str)> {
+        let mut required = Vec::new();
+        let tools = self.tools.iter().flatten();
+        if tools
+            .clone()
+            .any(|tool| matches!(tool, ToolDefinition::CodeExecution(_)))
+        {
+            required.push((AnthropicBeta::CodeExecution, "The code execution tool"));
+        }
+        let content = self.messages.iter().flat_map(|message| &message.content);
+        if content
+            .clone()
+            .any(|content| content.any_block(&|block| matches!(block, Content::SearchResult(_))))
+        {
+            required.push((AnthropicBeta::SearchResults, "Search result blocks"));
+        }
+        if content
+            .clone()
+            .any(|content| content.any_block(&Content::has_file_source))
+        {
+            required.push((AnthropicBeta::FilesApi, "File sources"));
+        }
+        required
+    }
+
+    /// Set the tools the model may use, custom or server tools.
+    pub fn with_tools<T: Into<ToolDefinition>>(
+        mut self,
+        tools: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.tools = Some(tools.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a tool to the tools the model may use.
+    pub fn with_tool(mut self, tool: impl Into<ToolDefinition>) -> Self {
+        self.tools.get_or_insert_with(Vec::new).push(tool.into());
+        self
+    }
+
+    /// Mark the last block of each of the last `turns` user messages as a cache breakpoint.
+    ///
+    /// In a multi-turn conversation this caches the conversation up to the new message and lets the next request read the previous prefix from the cache. The API accepts up to 4 breakpoints per request, including those set on tools and the system prompt.
+    pub fn with_cached_turns(mut self, turns: usize) -> Self {
+        self.messages
+            .iter_mut()
+            .rev()
+            .filter(|message| message.role == Role::User)
+            .take(turns)
+            .filter_map(|message| message.content.last_mut()?.cache_control_mut())
+            .for_each(|cache_control| *cache_control = Some(CacheControl::ephemeral()));
+        self
+    }
+
     pub fn with_temperature(mut self, temperature: f32) -> Self {
         self.temperature = Some(temperature);
         self
     }
 
-    pub fn with_top_k(mut self, top_k: i8) -> Self {
+    pub fn with_top_k(mut self, top_k: u32) -> Self {
         self.top_k = Some(top_k);
         self
     }
 
-    pub fn with_top_p(mut self, top_p: i8) -> Self {
+    pub fn with_top_p(mut self, top_p: f32) -> Self {
         self.top_p = Some(top_p);
         self
     }
+
+    /// Whether `input_tokens` plus **max_tokens** fit in the context window of the model, and **max_tokens** is within its output limit.
+    ///
+    /// Always true for limits unknown to the crate, e.g. of custom models.
+    pub fn fits_context_window(&self, input_tokens: u32) -> bool {
+        let total = input_tokens.saturating_add(self.max_tokens);
+        self.model
+            .max_output_tokens_with_betas(&self.betas)
+            .is_none_or(|limit| self.max_tokens <= limit)
+            && self
+                .model
+                .context_window()
+                .is_none_or(|limit| total <= limit)
+    }
+
+    /// Check the request locally before sending it, to fail early with a descriptive error.
+    ///
+    /// Messages must alternate between user and assistant roles, starting with a user message. Limits unknown to the crate, e.g. of custom models, are not checked. Only the betas of the request are taken into account, see [`MessageRequest::validate_with_betas`].
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.validate_with_betas(&[])
+    }
+
+    /// Check the request like [`MessageRequest::validate`], with `client_betas` enabled in addition to the betas of the request.
+    ///
+    /// [`AnthropicBeta::Output128k`] raises the output limit of Claude 3.7 Sonnet and [`AnthropicBeta::InterleavedThinking`] lets the thinking budget exceed **max_tokens**.
+    pub fn validate_with_betas(
+        &self,
+        client_betas: &[AnthropicBeta],
+    ) -> Result<(), ValidationError> {
+        let betas: Vec<AnthropicBeta> = self.betas.iter().chain(client_betas).cloned().collect();
+        if let Some(temperature) = self.temperature {
+            if !(0.0..=1.0).contains(&temperature) {
+                return Err(ValidationError::Temperature(temperature));
+            }
+        }
+        if let Some(top_p) = self.top_p {
+            if !(0.0..=1.0).contains(&top_p) {
+                return Err(ValidationError::TopP(top_p));
+            }
+        }
+        if self.max_tokens == 0 {
+            return Err(ValidationError::MaxTokens {
+                max_tokens: 0,
+                limit: self.model.max_output_tokens(),
+            });
+        }
+        if let Some(limit) = self.model.max_output_tokens_with_betas(&betas) {
+            if self.max_tokens > limit {
+                return Err(ValidationError::MaxTokens {
+                    max_tokens: self.max_tokens,
+                    limit: Some(limit),
+                });
+            }
+        }
+        if let Some(Thinking::Enabled { budget_tokens }) = self.thinking {
+            let interleaved = betas.contains(&AnthropicBeta::InterleavedThinking);
+            if budget_tokens < MIN_THINKING_BUDGET
+                || (budget_tokens >= self.max_tokens && !interleaved)
+            {
+                return Err(ValidationError::ThinkingBudget {
+                    budget_tokens,
+                    max_tokens: self.max_tokens,
+                });
+            }
+        }
+
+        let Some(first) = self.messages.first() else {
+            return Err(ValidationError::NoMessages);
+        };
+        if first.role != Role::User {
+            return Err(ValidationError::FirstMessageRole);
+        }
+        for (index, pair) in self.messages.windows(2).enumerate() {
+            if pair[0].role == pair[1].role {
+                return Err(ValidationError::RoleAlternation {
+                    index: index + 1,
+                    role: pair[1].role.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for MessageRequest {
@@ -160,6 +822,9 @@ impl Default for MessageRequest {
             stop_sequences: None,
             stream: false,
             system: None,
+            thinking: None,
+            betas: Vec::new(),
+            tools: None,
             temperature: None,
             top_k: None,
             top_p: None,
@@ -167,8 +832,19 @@ impl Default for MessageRequest {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Thinking {
+    /// Let the model reason before answering, using up to **budget_tokens** tokens.
+    Enabled {
+        budget_tokens: u32,
+    },
+    Disabled,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct MessageMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
 }
 
@@ -185,6 +861,18 @@ pub struct MessageResponse {
     pub usage: TokenUsage,
 }
 
+/// Turn a response into an assistant message to continue the conversation.
+///
+/// All content blocks are kept, so the thinking blocks and their signatures are sent back along with tool results.
+impl From<MessageResponse> for Message {
+    fn from(response: MessageResponse) -> Self {
+        Self {
+            role: Role::Assistant,
+            content: response.content,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum MessageType {
@@ -218,6 +906,8 @@ pub enum StopReason {
     MaxTokens,
     StopSequence,
     ToolUse,
+    /// A server tool ran for too long. Send the response back as is to let the model continue.
+    PauseTurn,
 }
 
 impl fmt::Display for StopReason {
@@ -227,6 +917,7 @@ impl fmt::Display for StopReason {
             Self::MaxTokens => write!(f, "max_tokens"),
             Self::StopSequence => write!(f, "stop_sequence"),
             Self::ToolUse => write!(f, "tool_use"),
+            Self::PauseTurn => write!(f, "pause_turn"),
         }
     }
 }
@@ -241,13 +932,30 @@ pub enum ContentType {
 pub struct TokenUsage {
     pub input_tokens: u32,
     pub output_tokens: u32,
+
+    /// Number of input tokens written to the cache, a cache miss.
     pub cache_creation_input_tokens: Option<u32>,
+
+    /// Number of input tokens read from the cache, a cache hit.
     pub cache_read_input_tokens: Option<u32>,
+
+    /// Number of server tool calls, billed separately.
+    pub server_tool_use: Option<ServerToolUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServerToolUsage {
+    #[serde(default)]
+    pub web_search_requests: u32,
+
+    #[serde(default)]
+    pub code_execution_requests: u32,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::completion::tool::CodeExecutionTool;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -315,7 +1023,7 @@ mod tests {
         let request = MessageRequest::default();
         assert_eq!(request.top_k, None);
 
-        let top_k: i8 = 1;
+        let top_k: u32 = 40;
         let request = request.with_top_k(top_k);
         assert_eq!(request.top_k, Some(top_k));
     }
@@ -325,7 +1033,7 @@ mod tests {
         let request = MessageRequest::default();
         assert_eq!(request.top_p, None);
 
-        let top_p: i8 = 1;
+        let top_p: f32 = 0.9;
         let request = request.with_top_p(top_p);
         assert_eq!(request.top_p, Some(top_p));
     }
@@ -334,10 +1042,7 @@ mod tests {
     fn should_serialize_message() {
         let message = Message {
             role: Role::User,
-            content: vec![Content {
-                content_type: ContentType::Text,
-                text: "Hello World".to_string(),
-            }],
+            content: vec![Content::text("Hello World")],
         };
         assert_eq!(
             serde_json::to_value(&message).unwrap(),
@@ -352,10 +1057,7 @@ mod tests {
 
         let message = Message {
             role: Role::Assistant,
-            content: vec![Content {
-                content_type: ContentType::Text,
-                text: "Hello World".to_string(),
-            }],
+            content: vec![Content::text("Hello World")],
         };
         assert_eq!(
             serde_json::to_value(&message).unwrap(),
@@ -380,13 +1082,7 @@ mod tests {
         });
         let message: Message = serde_json::from_value(json).unwrap();
         assert_eq!(message.role, Role::User);
-        assert_eq!(
-            message.content,
-            vec![Content {
-                content_type: ContentType::Text,
-                text: "Hello World".to_string(),
-            }]
-        );
+        assert_eq!(message.content, vec![Content::text("Hello World")]);
 
         let json = serde_json::json!({
             "role": "assistant",
@@ -397,12 +1093,442 @@ mod tests {
         });
         let message: Message = serde_json::from_value(json).unwrap();
         assert_eq!(message.role, Role::Assistant);
+        assert_eq!(message.content, vec![Content::text("Hello World")]);
+    }
+
+    #[test]
+    fn should_serialize_content_blocks() {
+        let content = vec![
+            Content::image_base64("image/png", "iVBORw0KGgo="),
+            Content::text("What is in this image?").with_cache_control(CacheControl::ephemeral()),
+            Content::tool_use(
+                "toolu_01",
+                "get_weather",
+                serde_json::json!({ "city": "Paris" }),
+            ),
+            Content::tool_result("toolu_01", "15 degrees"),
+        ];
+        assert_eq!(
+            serde_json::to_value(&content).unwrap(),
+            serde_json::json!([
+                {
+                    "type": "image",
+                    "source": { "type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo=" }
+                },
+                {
+                    "type": "text",
+                    "text": "What is in this image?",
+                    "cache_control": { "type": "ephemeral" }
+                },
+                {
+                    "type": "tool_use",
+                    "id": "toolu_01",
+                    "name": "get_weather",
+                    "input": { "city": "Paris" }
+                },
+                { "type": "tool_result", "tool_use_id": "toolu_01", "content": "15 degrees" }
+            ])
+        );
+    }
+
+    #[test]
+    fn should_mark_last_turns_as_cache_breakpoints() {
+        let message = |role: Role, text: &str| Message {
+            role,
+            content: vec![Content::text("context"), Content::text(text)],
+        };
+        let request = MessageRequest::new(
+            ClaudeModel::Claude35Sonnet,
+            1024,
+            vec![
+                message(Role::User, "first"),
+                message(Role::Assistant, "answer"),
+                message(Role::User, "second"),
+                message(Role::Assistant, "answer"),
+                message(Role::User, "third"),
+            ],
+        )
+        .with_cached_turns(2);
+
+        let cached: Vec<&str> = request
+            .messages
+            .iter()
+            .flat_map(|message| &message.content)
+            .filter(|content| content.cache_control().is_some())
+            .filter_map(Content::as_text)
+            .collect();
+        assert_eq!(cached, vec!["second", "third"]);
+    }
+
+    #[test]
+    fn should_set_thinking() {
+        let request = MessageRequest::default().with_thinking(2048);
+        assert_eq!(
+            serde_json::to_value(&request).unwrap()["thinking"],
+            serde_json::json!({ "type": "enabled", "budget_tokens": 2048 })
+        );
+    }
+
+    #[test]
+    fn should_deserialize_thinking_blocks() {
+        let json = serde_json::json!([
+            { "type": "thinking", "thinking": "Let me check the weather", "signature": "EqQBCgIYAhIM" },
+            { "type": "redacted_thinking", "data": "EmwKAhgBEgy3va3pzix" },
+            { "type": "tool_use", "id": "toolu_01", "name": "get_weather", "input": { "city": "Paris" } }
+        ]);
+        let content: Vec<Content> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            content[0],
+            Content::Thinking(ThinkingBlock {
+                thinking: "Let me check the weather".to_string(),
+                signature: "EqQBCgIYAhIM".to_string(),
+            })
+        );
+        assert_eq!(
+            content[1],
+            Content::RedactedThinking(RedactedThinkingBlock {
+                data: "EmwKAhgBEgy3va3pzix".to_string(),
+            })
+        );
+        assert_eq!(serde_json::to_value(&content).unwrap(), json);
+    }
+
+    #[test]
+    fn should_skip_unset_fields() {
+        let request = MessageRequest::new(ClaudeModel::Claude3Haiku, 1024, Vec::new());
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({
+                "model": "claude-3-haiku-20240307",
+                "max_tokens": 1024,
+                "messages": [],
+                "stream": false
+            })
+        );
+    }
+
+    #[test]
+    fn should_validate_request() {
+        let user = Message {
+            role: Role::User,
+            content: vec![Content::text("Hello")],
+        };
+        let assistant = Message {
+            role: Role::Assistant,
+            content: vec![Content::text("Hi")],
+        };
+        let request = MessageRequest::new(
+            ClaudeModel::Claude3Haiku,
+            1024,
+            vec![user.clone(), assistant.clone(), user.clone()],
+        );
+        assert_eq!(request.validate(), Ok(()));
+
+        assert_eq!(
+            request.clone().with_top_p(1.5).validate(),
+            Err(ValidationError::TopP(1.5))
+        );
+        assert_eq!(
+            request.clone().with_temperature(-0.1).validate(),
+            Err(ValidationError::Temperature(-0.1))
+        );
+
+        let mut too_long = request.clone();
+        too_long.max_tokens = 8192;
+        assert_eq!(
+            too_long.validate(),
+            Err(ValidationError::MaxTokens {
+                max_tokens: 8192,
+                limit: Some(4096)
+            })
+        );
+
+        assert_eq!(
+            request.clone().with_thinking(512).validate(),
+            Err(ValidationError::ThinkingBudget {
+                budget_tokens: 512,
+                max_tokens: 1024
+            })
+        );
+
+        let mut repeated = request.clone();
+        repeated.messages = vec![user.clone(), user.clone()];
+        assert_eq!(
+            repeated.validate().unwrap_err().to_string(),
+            "Message 1 has the same role as the previous message: user"
+        );
+
+        let mut assistant_first = request;
+        assistant_first.messages = vec![assistant, user];
+        assert_eq!(
+            assistant_first.validate(),
+            Err(ValidationError::FirstMessageRole)
+        );
+    }
+
+    #[test]
+    fn should_validate_with_output_128k_beta() {
+        let message = Message {
+            role: Role::User,
+            content: vec![Content::text("Hello")],
+        };
+        let request = MessageRequest::new(ClaudeModel::Claude37Sonnet, 100_000, vec![message]);
+        assert_eq!(
+            request.validate(),
+            Err(ValidationError::MaxTokens {
+                max_tokens: 100_000,
+                limit: Some(64_000)
+            })
+        );
+        assert_eq!(
+            request.validate_with_betas(&[AnthropicBeta::Output128k]),
+            Ok(())
+        );
+        assert_eq!(
+            request.with_beta(AnthropicBeta::Output128k).validate(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn should_validate_with_interleaved_thinking_beta() {
+        let message = Message {
+            role: Role::User,
+            content: vec![Content::text("Hello")],
+        };
+        let request = MessageRequest::new(ClaudeModel::ClaudeSonnet4, 2048, vec![message])
+            .with_thinking(4096);
+        assert_eq!(
+            request.validate(),
+            Err(ValidationError::ThinkingBudget {
+                budget_tokens: 4096,
+                max_tokens: 2048
+            })
+        );
+        assert_eq!(
+            request.validate_with_betas(&[AnthropicBeta::InterleavedThinking]),
+            Ok(())
+        );
+        assert_eq!(
+            request
+                .with_thinking(512)
+                .validate_with_betas(&[AnthropicBeta::InterleavedThinking]),
+            Err(ValidationError::ThinkingBudget {
+                budget_tokens: 512,
+                max_tokens: 2048
+            })
+        );
+    }
+
+    #[test]
+    fn should_serialize_documents() {
+        let content: Content = DocumentBlock::text("The grass is green. The sky is blue.")
+            .with_title("Facts")
+            .with_citations(true)
+            .into();
+        assert_eq!(
+            serde_json::to_value(&content).unwrap(),
+            serde_json::json!({
+                "type": "document",
+                "source": {
+                    "type": "text",
+                    "media_type": "text/plain",
+                    "data": "The grass is green. The sky is blue."
+                },
+                "title": "Facts",
+                "citations": { "enabled": true }
+            })
+        );
+
+        let content: Content = SearchResultBlock::new(
+            "https://example.com/docs",
+            "Docs",
+            vec![Content::text("Install with cargo add")],
+        )
+        .with_citations(true)
+        .into();
         assert_eq!(
-            message.content,
-            vec![Content {
-                content_type: ContentType::Text,
-                text: "Hello World".to_string(),
+            serde_json::to_value(&content).unwrap(),
+            serde_json::json!({
+                "type": "search_result",
+                "source": "https://example.com/docs",
+                "title": "Docs",
+                "content": [{ "type": "text", "text": "Install with cargo add" }],
+                "citations": { "enabled": true }
+            })
+        );
+    }
+
+    #[test]
+    fn should_deserialize_text_with_citations() {
+        let json = serde_json::json!({
+            "type": "text",
+            "text": "the grass is green",
+            "citations": [{
+                "type": "content_block_location",
+                "cited_text": "The grass is green.",
+                "document_index": 0,
+                "document_title": null,
+                "start_block_index": 0,
+                "end_block_index": 1
             }]
+        });
+        match serde_json::from_value::<Content>(json).unwrap() {
+            Content::Text(block) => {
+                let citations = block.citations.unwrap();
+                assert_eq!(citations.len(), 1);
+                assert_eq!(citations[0].cited_text(), "The grass is green.");
+            }
+            other => panic!("Expected text block, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_deserialize_server_tool_blocks() {
+        let json = serde_json::json!({
+            "id": "msg_01",
+            "type": "message",
+            "role": "assistant",
+            "model": "claude-sonnet-4-20250514",
+            "content": [
+                {
+                    "type": "server_tool_use",
+                    "id": "srvtoolu_01",
+                    "name": "web_search",
+                    "input": { "query": "rust serde" }
+                },
+                {
+                    "type": "web_search_tool_result",
+                    "tool_use_id": "srvtoolu_01",
+                    "content": [{
+                        "type": "web_search_result",
+                        "url": "https://serde.rs",
+                        "title": "Serde",
+                        "encrypted_content": "EqgfCioIARgBIiQ3YTAwMjY1Mi1",
+                        "page_age": "April 30, 2025"
+                    }]
+                },
+                {
+                    "type": "code_execution_tool_result",
+                    "tool_use_id": "srvtoolu_02",
+                    "content": {
+                        "type": "code_execution_result",
+                        "stdout": "4\n",
+                        "stderr": "",
+                        "return_code": 0,
+                        "content": []
+                    }
+                }
+            ],
+            "stop_reason": "pause_turn",
+            "stop_sequence": null,
+            "usage": {
+                "input_tokens": 105,
+                "output_tokens": 20,
+                "server_tool_use": { "web_search_requests": 1 }
+            }
+        });
+        let response: MessageResponse = serde_json::from_value(json).unwrap();
+
+        assert!(
+            matches!(&response.content[0], Content::ServerToolUse(block) if block.name == "web_search")
+        );
+        match &response.content[1] {
+            Content::WebSearchToolResult(block) => match &block.content {
+                WebSearchToolResultContent::Results(results) => {
+                    assert_eq!(results[0].url, "https://serde.rs")
+                }
+                other => panic!("Expected search results, got {:?}", other),
+            },
+            other => panic!("Expected web search result, got {:?}", other),
+        }
+        assert!(matches!(
+            &response.content[2],
+            Content::CodeExecutionToolResult(CodeExecutionToolResultBlock {
+                content: CodeExecutionToolResultContent::CodeExecutionResult { return_code: 0, .. },
+                ..
+            })
+        ));
+        assert_eq!(response.stop_reason, Some(StopReason::PauseTurn));
+        assert_eq!(
+            response.usage.server_tool_use.unwrap().web_search_requests,
+            1
+        );
+    }
+
+    #[test]
+    fn should_deserialize_web_search_error() {
+        let json = serde_json::json!({
+            "type": "web_search_tool_result",
+            "tool_use_id": "srvtoolu_01",
+            "content": { "type": "web_search_tool_result_error", "error_code": "max_uses_exceeded" }
+        });
+        match serde_json::from_value::<Content>(json).unwrap() {
+            Content::WebSearchToolResult(block) => assert_eq!(
+                block.content,
+                WebSearchToolResultContent::Error(ServerToolError {
+                    error_type: "web_search_tool_result_error".to_string(),
+                    error_code: "max_uses_exceeded".to_string(),
+                })
+            ),
+            other => panic!("Expected web search result, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_list_required_betas() {
+        let search_result = SearchResultBlock::new(
+            "https://example.com/docs",
+            "Docs",
+            vec![Content::text("Install with cargo add")],
+        );
+        let message = Message {
+            role: Role::User,
+            content: vec![Content::ToolResult(ToolResultBlock {
+                tool_use_id: "toolu_01".to_string(),
+                content: Some(ToolResultContent::Blocks(vec![search_result.into()])),
+                is_error: None,
+                cache_control: None,
+            })],
+        };
+        let request = MessageRequest::new(ClaudeModel::ClaudeSonnet4, 1024, vec![message])
+            .with_tool(CodeExecutionTool::new())
+            .with_beta(AnthropicBeta::CodeExecution)
+            .with_beta(AnthropicBeta::CodeExecution);
+
+        assert_eq!(request.betas, vec![AnthropicBeta::CodeExecution]);
+        let required: Vec<AnthropicBeta> = request
+            .required_betas()
+            .into_iter()
+            .map(|(beta, _)| beta)
+            .collect();
+        assert_eq!(
+            required,
+            vec![AnthropicBeta::CodeExecution, AnthropicBeta::SearchResults]
+        );
+
+        let message = Message {
+            role: Role::User,
+            content: vec![
+                DocumentBlock::file("file_011CNha8iCJcU1wXNR6q4V8w").into(),
+                Content::text("Summarize the report"),
+            ],
+        };
+        let request = MessageRequest::new(ClaudeModel::ClaudeSonnet4, 1024, vec![message]);
+        assert_eq!(
+            serde_json::to_value(&request.messages[0].content[0]).unwrap(),
+            serde_json::json!({
+                "type": "document",
+                "source": { "type": "file", "file_id": "file_011CNha8iCJcU1wXNR6q4V8w" }
+            })
+        );
+        assert_eq!(
+            request.required_betas(),
+            vec![(AnthropicBeta::FilesApi, "File sources")]
         );
+        assert!(serde_json::to_value(&request)
+            .unwrap()
+            .get("betas")
+            .is_none());
     }
 }
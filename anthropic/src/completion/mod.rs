@@ -1,2 +1,5 @@
+pub mod citation;
 pub mod message;
 pub mod stream;
+pub mod tokens;
+pub mod tool;
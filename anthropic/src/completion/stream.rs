@@ -2,54 +2,128 @@ use core::fmt;
 use serde::{de::Error, Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::message::{MessageResponse, StopReason};
+use super::{
+    citation::Citation,
+    message::{Content, MessageResponse, ServerToolUsage, StopReason},
+};
+use crate::error::{AnthropicError, ApiErrorDetail};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum StreamEvent {
     Ping,
-    MessageStart { message: MessageResponse },
+    MessageStart {
+        message: MessageResponse,
+    },
     MessageDelta(MessageDelta),
     MessageStop,
     ContentBlockStart(ContentBlockStart),
     ContentBlockDelta(ContentBlockDelta),
     ContentBlockStop(ContentBlockStop),
-}
 
-impl FromStr for StreamEvent {
-    type Err = serde_json::Error;
+    /// An error sent by the API once the stream started, e.g. when overloaded.
+    Error {
+        error: ApiErrorDetail,
+    },
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let value: serde_json::Value = serde_json::from_str(s)?;
+impl StreamEvent {
+    /// Parse an event, or return **None** if its type is unknown to this version of the crate.
+    fn from_value(value: serde_json::Value) -> Result<Option<Self>, serde_json::Error> {
         let event_type = value["type"]
             .as_str()
             .ok_or_else(|| serde_json::Error::custom("Missing or invalid 'type' field"))?;
 
-        match event_type {
-            "ping" => Ok(StreamEvent::Ping),
+        let event = match event_type {
+            "ping" => StreamEvent::Ping,
             "message_start" => {
                 let message: MessageResponse = serde_json::from_value(value["message"].clone())?;
-                Ok(StreamEvent::MessageStart { message })
+                StreamEvent::MessageStart { message }
             }
-            "content_block_start" => {
-                let message: ContentBlockStart = serde_json::from_value(value)?;
-                Ok(StreamEvent::ContentBlockStart(message))
+            "content_block_start" => StreamEvent::ContentBlockStart(serde_json::from_value(value)?),
+            "content_block_delta" => StreamEvent::ContentBlockDelta(serde_json::from_value(value)?),
+            "content_block_stop" => StreamEvent::ContentBlockStop(serde_json::from_value(value)?),
+            "message_delta" => StreamEvent::MessageDelta(serde_json::from_value(value)?),
+            "message_stop" => StreamEvent::MessageStop,
+            "error" => {
+                let error: ApiErrorDetail = serde_json::from_value(value["error"].clone())?;
+                StreamEvent::Error { error }
             }
-            "content_block_delta" => {
-                let message: ContentBlockDelta = serde_json::from_value(value)?;
-                Ok(StreamEvent::ContentBlockDelta(message))
-            }
-            "content_block_stop" => {
-                let message: ContentBlockStop = serde_json::from_value(value)?;
-                Ok(StreamEvent::ContentBlockStop(message))
-            }
-            "message_delta" => {
-                let message: MessageDelta = serde_json::from_value(value)?;
-                Ok(StreamEvent::MessageDelta(message))
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+impl FromStr for StreamEvent {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: serde_json::Value = serde_json::from_str(s)?;
+        Self::from_value(value)?.ok_or_else(|| serde_json::Error::custom("Unknown event type"))
+    }
+}
+
+/// Incremental parser turning raw server-sent events bytes into stream events.
+///
+/// Events can be split across several network chunks so incomplete events are buffered until their terminating blank line is received. The **\r\n** and **\r** line endings are normalized to **\n**. Events of unknown types are skipped.
+#[derive(Debug, Default)]
+pub(crate) struct EventParser {
+    buffer: Vec<u8>,
+
+    /// Whether the last byte received is a **\r**, so that a following **\n** belongs to the same line ending.
+    after_cr: bool,
+}
+
+impl EventParser {
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<Result<StreamEvent, AnthropicError>> {
+        for &byte in bytes {
+            match byte {
+                b'\r' => {
+                    self.buffer.push(b'\n');
+                    self.after_cr = true;
+                }
+                b'\n' if self.after_cr => self.after_cr = false,
+                _ => {
+                    self.buffer.push(byte);
+                    self.after_cr = false;
+                }
             }
-            "message_stop" => Ok(StreamEvent::MessageStop),
-            _ => Ok(StreamEvent::MessageStop),
         }
+
+        let mut events = Vec::new();
+        while let Some(position) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+            let event: Vec<u8> = self.buffer.drain(..position + 2).collect();
+            events.extend(Self::parse_event(&event[..position]));
+        }
+        events
+    }
+
+    /// Parse the last event when the stream doesn't end with a blank line.
+    pub(crate) fn finish(&mut self) -> Vec<Result<StreamEvent, AnthropicError>> {
+        let event = std::mem::take(&mut self.buffer);
+        Self::parse_event(&event).into_iter().collect()
+    }
+
+    fn parse_event(event: &[u8]) -> Option<Result<StreamEvent, AnthropicError>> {
+        let event = match std::str::from_utf8(event) {
+            Ok(event) => event,
+            Err(err) => return Some(Err(AnthropicError::Utf8Error(err))),
+        };
+        let data = event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        if data.trim().is_empty() {
+            return None;
+        }
+        serde_json::from_str(&data)
+            .and_then(StreamEvent::from_value)
+            .map_err(|_| AnthropicError::InvalidStreamEvent)
+            .transpose()
     }
 }
 
@@ -67,19 +141,33 @@ pub struct MessageDeltaStop {
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct StreamUsageTokens {
+    /// Cumulative number of output tokens.
     pub output_tokens: u32,
+
+    pub input_tokens: Option<u32>,
+
+    /// Number of input tokens written to the cache, a cache miss.
+    pub cache_creation_input_tokens: Option<u32>,
+
+    /// Number of input tokens read from the cache, a cache hit.
+    pub cache_read_input_tokens: Option<u32>,
+
+    /// Cumulative number of server tool calls.
+    pub server_tool_use: Option<ServerToolUsage>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ContentBlockStart {
     pub index: i64,
-    pub content_block: ContentBlock,
+
+    /// The block being generated, with empty text, thinking or input completed by the following deltas.
+    pub content_block: Content,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ContentBlockDelta {
     pub index: i64,
-    pub delta: ContentBlock,
+    pub delta: ContentDelta,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -87,32 +175,61 @@ pub struct ContentBlockStop {
     pub index: i64,
 }
 
+/// An increment of the content block at the same index.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub struct ContentBlock {
-    #[serde(rename = "type")]
-    pub kind: ContentBlockKind,
-    pub text: String,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentDelta {
+    TextDelta {
+        text: String,
+    },
+
+    /// A fragment of the JSON input of a tool use block. Fragments are valid JSON only once concatenated.
+    InputJsonDelta {
+        partial_json: String,
+    },
+
+    ThinkingDelta {
+        thinking: String,
+    },
+
+    /// The signature of a thinking block, sent just before its **content_block_stop** event.
+    SignatureDelta {
+        signature: String,
+    },
+
+    /// A citation to add to the **citations** of the text block.
+    CitationsDelta {
+        citation: Citation,
+    },
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum ContentBlockKind {
-    Text,
-    TextDelta,
+impl ContentDelta {
+    /// The text of the delta, if it is a text delta.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::TextDelta { text } => Some(text),
+            _ => None,
+        }
+    }
 }
 
-impl fmt::Display for ContentBlockKind {
+impl fmt::Display for ContentDelta {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Text => write!(f, "text"),
-            Self::TextDelta => write!(f, "text_delta"),
+            Self::TextDelta { .. } => write!(f, "text_delta"),
+            Self::InputJsonDelta { .. } => write!(f, "input_json_delta"),
+            Self::ThinkingDelta { .. } => write!(f, "thinking_delta"),
+            Self::SignatureDelta { .. } => write!(f, "signature_delta"),
+            Self::CitationsDelta { .. } => write!(f, "citations_delta"),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{completion::message::RoleResponse, models::claude::ClaudeModel};
+    use crate::{
+        completion::message::RoleResponse, error::ApiErrorType, models::claude::ClaudeModel,
+    };
 
     use super::*;
     use pretty_assertions::assert_eq;
@@ -151,8 +268,7 @@ mod tests {
 
         if let StreamEvent::ContentBlockStart(content) = event {
             assert_eq!(content.index, 0);
-            assert_eq!(content.content_block.kind, ContentBlockKind::Text);
-            assert_eq!(content.content_block.text, "");
+            assert_eq!(content.content_block, Content::text(""));
         } else {
             panic!("Expected 'content_block_start' event");
         }
@@ -165,8 +281,62 @@ mod tests {
 
         if let StreamEvent::ContentBlockDelta(content) = event {
             assert_eq!(content.index, 0);
-            assert_eq!(content.delta.kind, ContentBlockKind::TextDelta);
-            assert_eq!(content.delta.text, "Hello!");
+            assert_eq!(
+                content.delta,
+                ContentDelta::TextDelta {
+                    text: "Hello!".to_string()
+                }
+            );
+        } else {
+            panic!("Expected 'content_block_delta' event");
+        }
+    }
+
+    #[test]
+    fn should_deserialize_thinking_events() {
+        let start = r#"{"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}"#;
+        let thinking = r#"{"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me solve this"}}"#;
+        let signature = r#"{"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"EqQBCgIYAhIM"}}"#;
+
+        match start.parse::<StreamEvent>().unwrap() {
+            StreamEvent::ContentBlockStart(content) => assert!(matches!(
+                content.content_block,
+                Content::Thinking(block) if block.thinking.is_empty() && block.signature.is_empty()
+            )),
+            other => panic!("Expected 'content_block_start' event, got {:?}", other),
+        }
+        match thinking.parse::<StreamEvent>().unwrap() {
+            StreamEvent::ContentBlockDelta(content) => assert_eq!(
+                content.delta,
+                ContentDelta::ThinkingDelta {
+                    thinking: "Let me solve this".to_string()
+                }
+            ),
+            other => panic!("Expected 'content_block_delta' event, got {:?}", other),
+        }
+        match signature.parse::<StreamEvent>().unwrap() {
+            StreamEvent::ContentBlockDelta(content) => assert_eq!(
+                content.delta,
+                ContentDelta::SignatureDelta {
+                    signature: "EqQBCgIYAhIM".to_string()
+                }
+            ),
+            other => panic!("Expected 'content_block_delta' event, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_deserialize_citations_delta_event() {
+        let raw = r#"{"type":"content_block_delta","index":1,"delta":{"type":"citations_delta","citation":{"type":"char_location","cited_text":"The grass is green.","document_index":0,"document_title":"Facts","start_char_index":0,"end_char_index":20}}}"#;
+        let event: StreamEvent = raw.parse().unwrap();
+
+        if let StreamEvent::ContentBlockDelta(content) = event {
+            match content.delta {
+                ContentDelta::CitationsDelta { citation } => {
+                    assert_eq!(citation.cited_text(), "The grass is green.")
+                }
+                other => panic!("Expected 'citations_delta', got {:?}", other),
+            }
         } else {
             panic!("Expected 'content_block_delta' event");
         }
@@ -198,10 +368,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn should_deserialize_message_delta_cache_usage() {
+        let raw = r#"{"type":"message_delta","delta":{"stop_reason":"end_turn","stop_sequence":null},"usage":{"input_tokens":12,"cache_creation_input_tokens":0,"cache_read_input_tokens":2048,"output_tokens":30}}"#;
+        let event: StreamEvent = raw.parse().unwrap();
+
+        if let StreamEvent::MessageDelta(content) = event {
+            assert_eq!(content.usage.input_tokens, Some(12));
+            assert_eq!(content.usage.cache_creation_input_tokens, Some(0));
+            assert_eq!(content.usage.cache_read_input_tokens, Some(2048));
+        } else {
+            panic!("Expected 'message_delta' event");
+        }
+    }
+
     #[test]
     fn should_deserialize_message_stop_event() {
         let raw = r#"{"type":"message_stop"}"#;
         let event: StreamEvent = raw.parse().unwrap();
         assert_eq!(event, StreamEvent::MessageStop);
     }
+
+    #[test]
+    fn should_deserialize_error_event() {
+        let raw = r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        let event: StreamEvent = raw.parse().unwrap();
+
+        match event {
+            StreamEvent::Error { error } => {
+                assert_eq!(error.error_type, ApiErrorType::Overloaded);
+                assert_eq!(error.message, "Overloaded");
+            }
+            other => panic!("Expected 'error' event, got {:?}", other),
+        }
+        assert!(r#"{"type":"future_event"}"#.parse::<StreamEvent>().is_err());
+    }
+
+    #[test]
+    fn should_parse_events_split_across_chunks() {
+        let raw = "event: content_block_delta\r\ndata: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hello!\"}}\r\n\r\nevent: future_event\r\ndata: {\"type\":\"future_event\"}\r\n\r\nevent: message_stop\r\ndata: {\"type\":\"message_stop\"}";
+        let (first, second) = raw.split_at(60);
+
+        let mut parser = EventParser::default();
+        assert!(parser.push(first.as_bytes()).is_empty());
+        let mut events: Vec<StreamEvent> = parser
+            .push(second.as_bytes())
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        events.extend(parser.finish().into_iter().map(Result::unwrap));
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            StreamEvent::ContentBlockDelta(content) => {
+                assert_eq!(content.delta.as_text(), Some("Hello!"))
+            }
+            other => panic!("Expected 'content_block_delta' event, got {:?}", other),
+        }
+        assert_eq!(events[1], StreamEvent::MessageStop);
+    }
+
+    #[test]
+    fn should_normalize_line_endings() {
+        let mut parser = EventParser::default();
+        assert!(parser
+            .push(b"event: message_stop\rdata: {\"type\":\"message_stop\"}\r")
+            .is_empty());
+
+        let events = parser.push(b"\n\r\n");
+        assert!(matches!(events.as_slice(), [Ok(StreamEvent::MessageStop)]));
+    }
+
+    #[test]
+    fn should_report_invalid_events() {
+        let mut parser = EventParser::default();
+        let events = parser.push(b"event: message_start\ndata: {\"type\":\n\n");
+        assert!(matches!(
+            events.as_slice(),
+            [Err(AnthropicError::InvalidStreamEvent)]
+        ));
+    }
 }
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    completion::{
+        message::{
+            CodeExecutionToolResultContent, Content, DocumentSource, Message, MessageRequest,
+            System, Thinking, ToolResultContent, WebSearchToolResultContent,
+        },
+        tool::ToolDefinition,
+    },
+    models::claude::ClaudeModel,
+};
+
+/// Approximate number of characters per token of English text.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Tokens added around each message to mark its role.
+const MESSAGE_OVERHEAD_TOKENS: u32 = 4;
+
+/// Tokens of an image at the largest size used without resizing, about 1.15 megapixels.
+const IMAGE_TOKENS: u32 = 1600;
+
+/// Body of the **messages/count_tokens** endpoint, which only accepts the fields affecting the input.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CountTokensRequest<'a> {
+    model: &'a ClaudeModel,
+    messages: &'a [Message],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<&'a System>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<&'a [ToolDefinition]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<&'a Thinking>,
+}
+
+impl<'a> From<&'a MessageRequest> for CountTokensRequest<'a> {
+    fn from(request: &'a MessageRequest) -> Self {
+        Self {
+            model: &request.model,
+            messages: &request.messages,
+            system: request.system.as_ref(),
+            tools: request.tools.as_deref(),
+            thinking: request.thinking.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenCount {
+    /// The total number of tokens across the provided list of messages, system prompt, and tools.
+    pub input_tokens: u32,
+}
+
+/// Roughly estimate the number of input tokens of a request without calling the API.
+///
+/// The estimate counts one token per 4 characters of text and tool definitions, a fixed amount per image plus a small overhead per message. PDF and uploaded documents and the encrypted content of web search results are not counted. It is meant for tests and quick pre-flight checks, use [`Client::count_tokens`](crate::client::Client::count_tokens) for exact counts.
+pub fn estimate_input_tokens(request: &MessageRequest) -> u32 {
+    let system = match &request.system {
+        Some(System::Text(text)) => estimate_text_tokens(text),
+        Some(System::Structured(prompt)) => estimate_text_tokens(&prompt.text),
+        None => 0,
+    };
+    let tools: u32 = request
+        .tools
+        .iter()
+        .flatten()
+        .map(|tool| estimate_json_tokens(&serde_json::to_value(tool).unwrap_or_default()))
+        .sum();
+    let messages: u32 = request
+        .messages
+        .iter()
+        .map(|message| {
+            let content: u32 = message.content.iter().map(estimate_content_tokens).sum();
+            content + MESSAGE_OVERHEAD_TOKENS
+        })
+        .sum();
+    system + tools + messages
+}
+
+fn estimate_content_tokens(content: &Content) -> u32 {
+    match content {
+        Content::Text(block) => estimate_text_tokens(&block.text),
+        Content::Image(_) => IMAGE_TOKENS,
+        Content::ToolUse(block) | Content::ServerToolUse(block) => {
+            estimate_text_tokens(&block.name) + estimate_json_tokens(&block.input)
+        }
+        Content::WebSearchToolResult(block) => match &block.content {
+            WebSearchToolResultContent::Results(results) => results
+                .iter()
+                .map(|result| estimate_text_tokens(&result.title))
+                .sum(),
+            WebSearchToolResultContent::Error(_) => 0,
+        },
+        Content::CodeExecutionToolResult(block) => match &block.content {
+            CodeExecutionToolResultContent::CodeExecutionResult { stdout, stderr, .. } => {
+                estimate_text_tokens(stdout) + estimate_text_tokens(stderr)
+            }
+            CodeExecutionToolResultContent::CodeExecutionToolResultError { .. } => 0,
+        },
+        Content::ToolResult(block) => match &block.content {
+            Some(ToolResultContent::Text(text)) => estimate_text_tokens(text),
+            Some(ToolResultContent::Blocks(blocks)) => {
+                blocks.iter().map(estimate_content_tokens).sum()
+            }
+            None => 0,
+        },
+        Content::Thinking(block) => estimate_text_tokens(&block.thinking),
+        Content::RedactedThinking(block) => estimate_text_tokens(&block.data),
+        Content::Document(block) => match &block.source {
+            DocumentSource::Text { data, .. } => estimate_text_tokens(data),
+            DocumentSource::Content { content } => {
+                content.iter().map(estimate_content_tokens).sum()
+            }
+            DocumentSource::Base64 { .. }
+            | DocumentSource::Url { .. }
+            | DocumentSource::File { .. } => 0,
+        },
+        Content::SearchResult(block) => {
+            estimate_text_tokens(&block.title)
+                + block
+                    .content
+                    .iter()
+                    .map(estimate_content_tokens)
+                    .sum::<u32>()
+        }
+    }
+}
+
+fn estimate_json_tokens(value: &serde_json::Value) -> u32 {
+    estimate_text_tokens(&value.to_string())
+}
+
+fn estimate_text_tokens(text: &str) -> u32 {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::completion::{message::Role, tool::Tool};
+    use pretty_assertions::assert_eq;
+
+    fn request(text: &str) -> MessageRequest {
+        let message = Message {
+            role: Role::User,
+            content: vec![Content::text(text)],
+        };
+        MessageRequest::new(ClaudeModel::Claude3Haiku, 1024, vec![message])
+    }
+
+    #[test]
+    fn should_estimate_input_tokens() {
+        assert_eq!(estimate_input_tokens(&request("Hello, Claude")), 4 + 4);
+
+        let request = request("Hello, Claude").with_system(System::Text("Be concise".into()));
+        assert_eq!(estimate_input_tokens(&request), 3 + 4 + 4);
+    }
+
+    #[test]
+    fn should_estimate_images_and_tools() {
+        let mut request = request("Hello, Claude");
+        request.messages[0]
+            .content
+            .push(Content::image_url("https://example.com/cat.png"));
+        assert_eq!(estimate_input_tokens(&request), 4 + IMAGE_TOKENS + 4);
+
+        let tool = ToolDefinition::from(Tool::new("noop", serde_json::json!({})));
+        let tool_tokens = estimate_json_tokens(&serde_json::to_value(&tool).unwrap());
+        let request = request.with_tools(vec![tool]);
+        assert_eq!(
+            estimate_input_tokens(&request),
+            tool_tokens + 4 + IMAGE_TOKENS + 4
+        );
+    }
+
+    #[test]
+    fn should_check_context_window() {
+        let mut request = request("Hello, Claude");
+        assert!(request.fits_context_window(198_976));
+        assert!(!request.fits_context_window(198_977));
+
+        request.max_tokens = 8192;
+        assert!(!request.fits_context_window(0));
+    }
+
+    #[test]
+    fn should_only_serialize_input_fields() {
+        let request = request("Hello, Claude").with_temperature(0.5);
+        assert_eq!(
+            serde_json::to_value(CountTokensRequest::from(&request)).unwrap(),
+            serde_json::json!({
+                "model": "claude-3-haiku-20240307",
+                "messages": [{
+                    "role": "user",
+                    "content": [{ "type": "text", "text": "Hello, Claude" }]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn should_serialize_thinking() {
+        let request = request("Hello, Claude").with_thinking(1024);
+        assert_eq!(
+            serde_json::to_value(CountTokensRequest::from(&request)).unwrap()["thinking"],
+            serde_json::json!({ "type": "enabled", "budget_tokens": 1024 })
+        );
+    }
+}
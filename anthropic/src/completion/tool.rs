@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+
+use super::message::CacheControl;
+
+/// Definition of a tool the model may use.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Tool {
+    /// Name of the tool, used by the model in **tool_use** blocks.
+    pub name: String,
+
+    /// Description of what the tool does. The more detailed, the better the model uses it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// JSON schema of the input of the tool.
+    pub input_schema: serde_json::Value,
+
+    /// Cache breakpoint. Set on the last tool to cache all tool definitions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Tool {
+    pub fn new(name: impl Into<String>, input_schema: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            input_schema,
+            cache_control: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_cache_control(mut self, cache_control: CacheControl) -> Self {
+        self.cache_control = Some(cache_control);
+        self
+    }
+}
+
+/// A tool available to the model, either defined by the application or run by Anthropic.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum ToolDefinition {
+    /// A tool run by the application, whose calls are returned as **tool_use** blocks.
+    #[serde(rename = "custom")]
+    Custom(Tool),
+
+    /// Search the web, run by the API. Results are returned as **web_search_tool_result** blocks.
+    #[serde(rename = "web_search_20250305")]
+    WebSearch(WebSearchTool),
+
+    /// Run Python code in a sandbox, run by the API. Requires the **code-execution-2025-05-22** beta.
+    #[serde(rename = "code_execution_20250522")]
+    CodeExecution(CodeExecutionTool),
+}
+
+impl From<Tool> for ToolDefinition {
+    fn from(tool: Tool) -> Self {
+        Self::Custom(tool)
+    }
+}
+
+impl From<WebSearchTool> for ToolDefinition {
+    fn from(tool: WebSearchTool) -> Self {
+        Self::WebSearch(tool)
+    }
+}
+
+impl From<CodeExecutionTool> for ToolDefinition {
+    fn from(tool: CodeExecutionTool) -> Self {
+        Self::CodeExecution(tool)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebSearchTool {
+    /// Always **web_search**.
+    pub name: String,
+
+    /// Maximum number of searches per request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_uses: Option<u32>,
+
+    /// Only include results from these domains. Can't be set along with **blocked_domains**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_domains: Option<Vec<String>>,
+
+    /// Never include results from these domains. Can't be set along with **allowed_domains**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocked_domains: Option<Vec<String>>,
+
+    /// Location of the user, used to localize results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<UserLocation>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for WebSearchTool {
+    fn default() -> Self {
+        Self {
+            name: "web_search".to_string(),
+            max_uses: None,
+            allowed_domains: None,
+            blocked_domains: None,
+            user_location: None,
+            cache_control: None,
+        }
+    }
+}
+
+impl WebSearchTool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_uses(mut self, max_uses: u32) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    pub fn with_allowed_domains(mut self, domains: Vec<String>) -> Self {
+        self.allowed_domains = Some(domains);
+        self
+    }
+
+    pub fn with_blocked_domains(mut self, domains: Vec<String>) -> Self {
+        self.blocked_domains = Some(domains);
+        self
+    }
+
+    pub fn with_user_location(mut self, user_location: UserLocation) -> Self {
+        self.user_location = Some(user_location);
+        self
+    }
+}
+
+/// Approximate location of the user.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename = "approximate")]
+pub struct UserLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+
+    /// Two letter ISO country code e.g. **US**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+
+    /// IANA timezone e.g. **America/Los_Angeles**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodeExecutionTool {
+    /// Always **code_execution**.
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<CacheControl>,
+}
+
+impl Default for CodeExecutionTool {
+    fn default() -> Self {
+        Self {
+            name: "code_execution".to_string(),
+            cache_control: None,
+        }
+    }
+}
+
+impl CodeExecutionTool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_serialize_tool() {
+        let tool = Tool::new(
+            "get_weather",
+            serde_json::json!({
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"]
+            }),
+        )
+        .with_description("Get the current weather in a city")
+        .with_cache_control(CacheControl::ephemeral());
+
+        assert_eq!(
+            serde_json::to_value(&tool).unwrap(),
+            serde_json::json!({
+                "name": "get_weather",
+                "description": "Get the current weather in a city",
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "city": { "type": "string" } },
+                    "required": ["city"]
+                },
+                "cache_control": { "type": "ephemeral" }
+            })
+        );
+    }
+
+    #[test]
+    fn should_serialize_server_tools() {
+        let tools: Vec<ToolDefinition> = vec![
+            Tool::new("noop", serde_json::json!({ "type": "object" })).into(),
+            WebSearchTool::new()
+                .with_max_uses(5)
+                .with_allowed_domains(vec!["docs.rs".to_string()])
+                .with_user_location(UserLocation {
+                    country: Some("FR".to_string()),
+                    ..Default::default()
+                })
+                .into(),
+            CodeExecutionTool::new().into(),
+        ];
+        assert_eq!(
+            serde_json::to_value(&tools).unwrap(),
+            serde_json::json!([
+                { "type": "custom", "name": "noop", "input_schema": { "type": "object" } },
+                {
+                    "type": "web_search_20250305",
+                    "name": "web_search",
+                    "max_uses": 5,
+                    "allowed_domains": ["docs.rs"],
+                    "user_location": { "type": "approximate", "country": "FR" }
+                },
+                { "type": "code_execution_20250522", "name": "code_execution" }
+            ])
+        );
+    }
+}
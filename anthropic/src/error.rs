@@ -1,8 +1,8 @@
 use std::str::Utf8Error;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::client::ApiVersionError;
+use crate::{beta::AnthropicBeta, client::ApiVersionError, completion::message::Role};
 
 #[derive(Debug, thiserror::Error)]
 pub enum AnthropicError {
@@ -24,9 +24,6 @@ pub enum AnthropicError {
     #[error("Invalid header value: {0}")]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
 
-    #[error("Model not supported: {0}")]
-    ModelNotSupported(String),
-
     #[error("Missing API key {0}")]
     MissingApiKey(&'static str),
 
@@ -36,10 +33,47 @@ pub enum AnthropicError {
     #[error("UTF8 Error: {0}")]
     Utf8Error(#[from] Utf8Error),
 
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid request: {0}")]
+    Validation(#[from] ValidationError),
+
+    #[error("{feature} requires the {beta} beta")]
+    BetaRequired {
+        beta: AnthropicBeta,
+        feature: &'static str,
+    },
+
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 }
 
+/// A request rejected by [`MessageRequest::validate`](crate::completion::message::MessageRequest::validate).
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ValidationError {
+    #[error("temperature must be between 0.0 and 1.0, got {0}")]
+    Temperature(f32),
+
+    #[error("top_p must be between 0.0 and 1.0, got {0}")]
+    TopP(f32),
+
+    #[error("max_tokens must be between 1 and the limit of the model ({}), got {max_tokens}", .limit.map_or("unknown".to_string(), |limit| limit.to_string()))]
+    MaxTokens { max_tokens: u32, limit: Option<u32> },
+
+    #[error("thinking budget must be at least 1024 tokens and less than max_tokens ({max_tokens}), got {budget_tokens}")]
+    ThinkingBudget { budget_tokens: u32, max_tokens: u32 },
+
+    #[error("At least one message is required")]
+    NoMessages,
+
+    #[error("The first message must have the user role")]
+    FirstMessageRole,
+
+    #[error("Message {index} has the same role as the previous message: {role}")]
+    RoleAlternation { index: usize, role: Role },
+}
+
 #[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
 #[error("Error response: {error_type} {error}")]
 pub struct ApiErrorResponse {
@@ -48,7 +82,7 @@ pub struct ApiErrorResponse {
     pub error: ApiErrorDetail,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
 #[error("Api error: {error_type} {message}")]
 pub struct ApiErrorDetail {
     #[serde(rename = "type")]
@@ -56,7 +90,7 @@ pub struct ApiErrorDetail {
     pub message: String,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
 pub enum ApiErrorType {
     #[error("invalid_request_error")]
     #[serde(rename = "invalid_request_error")]
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+/// A file uploaded with the Files API, referenced by its ID in document and image blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub file_type: String,
+
+    /// Original name of the uploaded file.
+    pub filename: String,
+
+    /// MIME type of the file e.g. **application/pdf**.
+    pub mime_type: String,
+
+    /// Size of the file in bytes.
+    pub size_bytes: u64,
+
+    /// RFC 3339 datetime string representing the time at which the file was created.
+    pub created_at: String,
+
+    /// Whether the file can be downloaded. Only files created by tools, e.g. code execution, are downloadable.
+    #[serde(default)]
+    pub downloadable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListFiles {
+    pub data: Vec<FileMetadata>,
+
+    /// Whether there are more results in the requested page direction.
+    pub has_more: bool,
+
+    /// First ID in the data list. Can be used as the **before_id** of the previous page.
+    pub first_id: Option<String>,
+
+    /// Last ID in the data list. Can be used as the **after_id** of the next page.
+    pub last_id: Option<String>,
+}
+
+/// Pagination parameters of [`Client::list_files`](crate::client::Client::list_files).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListFilesParams {
+    /// Return the page of results immediately before this file ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+
+    /// Return the page of results immediately after this file ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+
+    /// Number of files per page.
+    ///
+    /// Defaults to 20. Ranges from 1 to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl ListFilesParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    pub fn with_after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeletedFile {
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub file_type: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_deserialize_file_metadata() {
+        let json = serde_json::json!({
+            "id": "file_011CNha8iCJcU1wXNR6q4V8w",
+            "type": "file",
+            "filename": "report.pdf",
+            "mime_type": "application/pdf",
+            "size_bytes": 1024000,
+            "created_at": "2025-01-01T00:00:00Z"
+        });
+        let file: FileMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(file.filename, "report.pdf");
+        assert_eq!(file.size_bytes, 1_024_000);
+        assert!(!file.downloadable);
+    }
+}
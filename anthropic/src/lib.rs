@@ -1,11 +1,14 @@
 #![doc = include_str!("../README.md")]
 
+pub mod batches;
+pub mod beta;
 pub mod client;
 pub mod completion;
 pub mod config;
 pub mod error;
+pub mod files;
 pub mod models;
 
 pub mod prelude {
-    pub use crate::{client, completion, config, error, models};
+    pub use crate::{batches, beta, client, completion, config, error, files, models};
 }
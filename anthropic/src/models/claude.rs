@@ -1,49 +1,144 @@
 use core::fmt;
-use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{convert::Infallible, str::FromStr};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+use crate::beta::AnthropicBeta;
+
+/// A Claude model.
+///
+/// Models unknown to this version of the crate are kept as [`ClaudeModel::Custom`], so responses from newer models still deserialize.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum ClaudeModel {
-    #[serde(rename = "claude-3-5-sonnet-20240620")]
+    #[default]
     Claude35Sonnet,
-    #[serde(rename = "claude-3-opus-20240229")]
     Claude3Opus,
-    #[serde(rename = "claude-3-sonnet-20240229")]
     Claude3Sonnet,
-    #[serde(rename = "claude-3-haiku-20240307")]
     Claude3Haiku,
+    Claude35SonnetV2,
+    Claude35Haiku,
+    Claude37Sonnet,
+    ClaudeSonnet4,
+    ClaudeOpus4,
+    /// Any other model ID or alias, sent as is.
+    Custom(String),
 }
 
+/// Aliases pointing to the latest snapshot of a model.
+///
+/// Aliases are kept as [`ClaudeModel::Custom`] and sent as is, so they keep floating. Their snapshot is only used to look up the limits of the model.
+const ALIASES: &[(&str, ClaudeModel)] = &[
+    ("claude-3-opus-latest", ClaudeModel::Claude3Opus),
+    ("claude-3-5-sonnet-latest", ClaudeModel::Claude35SonnetV2),
+    ("claude-3-5-haiku-latest", ClaudeModel::Claude35Haiku),
+    ("claude-3-7-sonnet-latest", ClaudeModel::Claude37Sonnet),
+    ("claude-sonnet-4-0", ClaudeModel::ClaudeSonnet4),
+    ("claude-opus-4-0", ClaudeModel::ClaudeOpus4),
+];
+
+const KNOWN_MODELS: &[ClaudeModel] = &[
+    ClaudeModel::Claude35Sonnet,
+    ClaudeModel::Claude3Opus,
+    ClaudeModel::Claude3Sonnet,
+    ClaudeModel::Claude3Haiku,
+    ClaudeModel::Claude35SonnetV2,
+    ClaudeModel::Claude35Haiku,
+    ClaudeModel::Claude37Sonnet,
+    ClaudeModel::ClaudeSonnet4,
+    ClaudeModel::ClaudeOpus4,
+];
+
 impl ClaudeModel {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             Self::Claude35Sonnet => "claude-3-5-sonnet-20240620",
             Self::Claude3Opus => "claude-3-opus-20240229",
             Self::Claude3Sonnet => "claude-3-sonnet-20240229",
             Self::Claude3Haiku => "claude-3-haiku-20240307",
+            Self::Claude35SonnetV2 => "claude-3-5-sonnet-20241022",
+            Self::Claude35Haiku => "claude-3-5-haiku-20241022",
+            Self::Claude37Sonnet => "claude-3-7-sonnet-20250219",
+            Self::ClaudeSonnet4 => "claude-sonnet-4-20250514",
+            Self::ClaudeOpus4 => "claude-opus-4-20250514",
+            Self::Custom(model) => model,
         }
     }
-}
 
-impl Default for ClaudeModel {
-    fn default() -> Self {
-        Self::Claude35Sonnet
+    /// Snapshot currently pointed to by an alias, or the model itself.
+    fn snapshot(&self) -> &Self {
+        match self {
+            Self::Custom(model) => ALIASES
+                .iter()
+                .find(|(alias, _)| alias == model)
+                .map_or(self, |(_, snapshot)| snapshot),
+            _ => self,
+        }
+    }
+
+    /// Maximum number of tokens of the input and the generated output combined.
+    ///
+    /// **None** for custom models other than known aliases.
+    pub fn context_window(&self) -> Option<u32> {
+        match self.snapshot() {
+            Self::Custom(_) => None,
+            _ => Some(200_000),
+        }
+    }
+
+    /// Maximum value of **max_tokens** accepted by the model.
+    ///
+    /// **None** for custom models other than known aliases.
+    pub fn max_output_tokens(&self) -> Option<u32> {
+        match self.snapshot() {
+            Self::Claude3Opus | Self::Claude3Sonnet | Self::Claude3Haiku => Some(4096),
+            Self::Claude35Sonnet | Self::Claude35SonnetV2 | Self::Claude35Haiku => Some(8192),
+            Self::ClaudeOpus4 => Some(32_000),
+            Self::Claude37Sonnet | Self::ClaudeSonnet4 => Some(64_000),
+            Self::Custom(_) => None,
+        }
+    }
+
+    /// Maximum value of **max_tokens** accepted by the model with `betas` enabled, e.g. **128000** for Claude 3.7 Sonnet with [`AnthropicBeta::Output128k`].
+    ///
+    /// **None** for custom models other than known aliases.
+    pub fn max_output_tokens_with_betas(&self, betas: &[AnthropicBeta]) -> Option<u32> {
+        match self.snapshot() {
+            Self::Claude37Sonnet if betas.contains(&AnthropicBeta::Output128k) => Some(128_000),
+            _ => self.max_output_tokens(),
+        }
+    }
+
+    /// Whether the model accepts images as input.
+    ///
+    /// **None** for custom models other than known aliases.
+    pub fn supports_vision(&self) -> Option<bool> {
+        match self.snapshot() {
+            Self::Custom(_) => None,
+            _ => Some(true),
+        }
+    }
+
+    /// Whether the model supports extended thinking.
+    ///
+    /// **None** for custom models other than known aliases.
+    pub fn supports_thinking(&self) -> Option<bool> {
+        match self.snapshot() {
+            Self::Claude37Sonnet | Self::ClaudeSonnet4 | Self::ClaudeOpus4 => Some(true),
+            Self::Custom(_) => None,
+            _ => Some(false),
+        }
     }
 }
 
 impl FromStr for ClaudeModel {
-    type Err = crate::error::AnthropicError;
+    type Err = Infallible;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "claude-3-5-sonnet-20240620" => Ok(Self::Claude35Sonnet),
-            "claude-3-opus-20240229" => Ok(Self::Claude3Opus),
-            "claude-3-sonnet-20240229" => Ok(Self::Claude3Sonnet),
-            "claude-3-haiku-20240307" => Ok(Self::Claude3Haiku),
-            _ => Err(crate::error::AnthropicError::ModelNotSupported(
-                s.to_string(),
-            )),
-        }
+        let model = KNOWN_MODELS
+            .iter()
+            .find(|model| model.as_str() == s)
+            .cloned()
+            .unwrap_or_else(|| Self::Custom(s.to_string()));
+        Ok(model)
     }
 }
 
@@ -53,10 +148,89 @@ impl fmt::Display for ClaudeModel {
     }
 }
 
+impl Serialize for ClaudeModel {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ClaudeModel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let model = String::deserialize(deserializer)?;
+        Ok(model.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+/// A model available through the API.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModelInfo {
+    pub id: ClaudeModel,
+
+    #[serde(rename = "type")]
+    pub model_type: String,
+
+    /// A human-readable name e.g. **Claude Sonnet 4**.
+    pub display_name: String,
+
+    /// RFC 3339 datetime string representing the time at which the model was released.
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListModels {
+    pub data: Vec<ModelInfo>,
+
+    /// Whether there are more results in the requested page direction.
+    pub has_more: bool,
+
+    /// First ID in the data list. Can be used as the **before_id** of the previous page.
+    pub first_id: Option<String>,
+
+    /// Last ID in the data list. Can be used as the **after_id** of the next page.
+    pub last_id: Option<String>,
+}
+
+/// Pagination parameters of [`Client::list_models`](crate::client::Client::list_models).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ListModelsParams {
+    /// Return the page of results immediately before this model ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+
+    /// Return the page of results immediately after this model ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+
+    /// Number of models per page.
+    ///
+    /// Defaults to 20. Ranges from 1 to 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl ListModelsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_before_id(mut self, before_id: impl Into<String>) -> Self {
+        self.before_id = Some(before_id.into());
+        self
+    }
+
+    pub fn with_after_id(mut self, after_id: impl Into<String>) -> Self {
+        self.after_id = Some(after_id.into());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::error::AnthropicError;
-
     use super::*;
     use pretty_assertions::assert_eq;
 
@@ -75,6 +249,10 @@ mod tests {
             ClaudeModel::Claude3Haiku.as_str(),
             "claude-3-haiku-20240307"
         );
+        assert_eq!(
+            serde_json::to_value(ClaudeModel::ClaudeSonnet4).unwrap(),
+            "claude-sonnet-4-20250514"
+        );
     }
 
     #[test]
@@ -95,13 +273,81 @@ mod tests {
             ClaudeModel::Claude3Haiku,
             ClaudeModel::from_str("claude-3-haiku-20240307").unwrap(),
         );
+        assert_eq!(
+            ClaudeModel::Claude37Sonnet,
+            serde_json::from_value(serde_json::json!("claude-3-7-sonnet-20250219")).unwrap(),
+        );
+    }
+
+    #[test]
+    fn should_keep_aliases_as_given() {
+        let model = ClaudeModel::from_str("claude-3-5-sonnet-latest").unwrap();
+        assert_eq!(
+            model,
+            ClaudeModel::Custom("claude-3-5-sonnet-latest".to_string())
+        );
+        assert_eq!(
+            serde_json::to_value(&model).unwrap(),
+            serde_json::json!("claude-3-5-sonnet-latest")
+        );
+        assert_eq!(model.max_output_tokens(), Some(8192));
+
+        let model = ClaudeModel::from_str("claude-sonnet-4-0").unwrap();
+        assert_eq!(model.as_str(), "claude-sonnet-4-0");
+        assert_eq!(model.supports_thinking(), Some(true));
+    }
+
+    #[test]
+    fn should_keep_unknown_models() {
+        let model = ClaudeModel::from_str("claude-future-model").unwrap();
+        assert_eq!(
+            model,
+            ClaudeModel::Custom("claude-future-model".to_string())
+        );
+        assert_eq!(serde_json::to_value(&model).unwrap(), "claude-future-model");
+        assert_eq!(model.context_window(), None);
+    }
+
+    #[test]
+    fn should_return_model_metadata() {
+        assert_eq!(ClaudeModel::Claude35Sonnet.context_window(), Some(200_000));
+        assert_eq!(ClaudeModel::Claude35Sonnet.max_output_tokens(), Some(8192));
+        assert_eq!(ClaudeModel::Claude3Haiku.max_output_tokens(), Some(4096));
+        assert_eq!(ClaudeModel::Claude37Sonnet.supports_thinking(), Some(true));
+        assert_eq!(ClaudeModel::Claude3Opus.supports_vision(), Some(true));
+        assert_eq!(
+            ClaudeModel::Claude37Sonnet.max_output_tokens_with_betas(&[AnthropicBeta::Output128k]),
+            Some(128_000)
+        );
+        assert_eq!(
+            ClaudeModel::ClaudeSonnet4.max_output_tokens_with_betas(&[AnthropicBeta::Output128k]),
+            Some(64_000)
+        );
     }
 
     #[test]
-    fn should_return_error_for_invalid_model() {
-        assert!(matches!(
-            ClaudeModel::from_str("claude-invalid-model"),
-            Err(AnthropicError::ModelNotSupported(_))
-        ));
+    fn should_deserialize_model_list() {
+        let json = serde_json::json!({
+            "data": [{
+                "type": "model",
+                "id": "claude-sonnet-4-20250514",
+                "display_name": "Claude Sonnet 4",
+                "created_at": "2025-05-22T00:00:00Z"
+            }, {
+                "type": "model",
+                "id": "claude-next-20990101",
+                "display_name": "Claude Next",
+                "created_at": "2099-01-01T00:00:00Z"
+            }],
+            "has_more": false,
+            "first_id": "claude-sonnet-4-20250514",
+            "last_id": "claude-next-20990101"
+        });
+        let models: ListModels = serde_json::from_value(json).unwrap();
+        assert_eq!(models.data[0].id, ClaudeModel::ClaudeSonnet4);
+        assert_eq!(
+            models.data[1].id,
+            ClaudeModel::Custom("claude-next-20990101".to_string())
+        );
     }
 }
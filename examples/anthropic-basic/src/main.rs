@@ -1,6 +1,6 @@
 use anthropic_rs::{
     client::Client,
-    completion::message::{Content, ContentType, Message, MessageRequest, Role},
+    completion::message::{Content, Message, MessageRequest, Role},
     config::Config,
     models::claude::ClaudeModel,
 };
@@ -18,10 +18,7 @@ async fn main() {
         max_tokens: 1024,
         messages: vec![Message {
             role: Role::User,
-            content: vec![Content {
-                content_type: ContentType::Text,
-                text: "Explain the theory of relativity".to_string(),
-            }],
+            content: vec![Content::text("Explain the theory of relativity")],
         }],
         ..Default::default()
     };
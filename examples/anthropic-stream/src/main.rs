@@ -1,7 +1,7 @@
 use anthropic_rs::{
     client::Client,
     completion::{
-        message::{Content, ContentType, Message, MessageRequest, Role},
+        message::{Content, Message, MessageRequest, Role},
         stream::StreamEvent,
     },
     config::Config,
@@ -24,10 +24,7 @@ async fn main() {
         max_tokens: 1024,
         messages: vec![Message {
             role: Role::User,
-            content: vec![Content {
-                content_type: ContentType::Text,
-                text: "Explain the theory of relativity".to_string(),
-            }],
+            content: vec![Content::text("Explain the theory of relativity")],
         }],
         ..Default::default()
     };
@@ -38,10 +35,16 @@ async fn main() {
         let event = event.unwrap();
         match event {
             StreamEvent::ContentBlockDelta(content) => {
-                print!("{}", content.delta.text);
-                std::io::stdout().flush().unwrap();
+                if let Some(text) = content.delta.as_text() {
+                    print!("{}", text);
+                    std::io::stdout().flush().unwrap();
+                }
             }
             StreamEvent::MessageStop => break,
+            StreamEvent::Error { error } => {
+                eprintln!("{}", error);
+                break;
+            }
             _ => {}
         }
     }
@@ -0,0 +1,39 @@
+use futures_util::StreamExt;
+use perplexity::{
+    client::{Client, CreateChatCompletion, Message, Model, Role},
+    config::Config,
+};
+use std::io::Write;
+
+#[tokio::main]
+async fn main() {
+    let api_key = std::env::var("PERPLEXITY_API_KEY")
+        .expect("environment variable PERPLEXITY_API_KEY should be defined");
+
+    let config = Config::new(api_key);
+    let client = Client::new(config).unwrap();
+
+    let messages: Vec<Message> = vec![Message {
+        role: Role::User,
+        content: "Find me the best pad thai restaurant in Bangkok".to_string(),
+    }];
+    let message = CreateChatCompletion::new(Model::Llama31SonarLargeOnline, messages);
+    let mut stream = client.stream_completion(message).await.unwrap();
+
+    let mut citations = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.unwrap();
+        if let Some(content) = chunk.content() {
+            print!("{}", content);
+            std::io::stdout().flush().unwrap();
+        }
+        if let Some(chunk_citations) = chunk.citations {
+            citations = chunk_citations;
+        }
+    }
+
+    println!();
+    for (index, citation) in citations.iter().enumerate() {
+        println!("[{}] {}", index + 1, citation);
+    }
+}
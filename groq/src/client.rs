@@ -1,16 +1,17 @@
 use reqwest::{
     header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
-    Client as ReqwestClient, Method, RequestBuilder, Response, StatusCode, Url,
+    Method, Url,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{chat::ChatClient, config::Config, error::Error};
+use crate::{
+    chat::ChatClient, config::Config, error::Error, response::ApiResponse, transport::Transport,
+};
 
 pub struct Client {
     api_key: String,
-    base_url: Url,
     pub chat: ChatClient,
-    http_client: ReqwestClient,
+    transport: Transport,
 }
 
 impl Client {
@@ -29,11 +30,12 @@ impl Client {
         let base_url =
             Url::parse(&config.base_url).map_err(|err| Error::UrlParse(err.to_string()))?;
 
+        let transport = Transport::new(base_url, http_client);
+
         Ok(Self {
             api_key: config.api_key,
-            base_url: base_url.clone(),
-            chat: ChatClient::new(base_url, http_client.clone()),
-            http_client,
+            chat: ChatClient::from_transport(transport.clone()),
+            transport,
         })
     }
 
@@ -42,70 +44,21 @@ impl Client {
     }
 
     pub fn base_url(&self) -> &str {
-        self.base_url.as_str()
+        self.transport.base_url().as_str()
     }
 
     pub async fn list_models(&self) -> Result<ListModelsResponse, Error> {
-        let models = self.request(Method::GET, "models")?.send().await?;
-        self.handle_response::<ListModelsResponse>(models).await
-    }
-
-    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
-        let url = self
-            .base_url
-            .join(path)
-            .map_err(|err| Error::UrlParse(err.to_string()))?;
-        Ok(self.http_client.request(method, url))
+        self.list_models_with_metadata()
+            .await
+            .map(ApiResponse::into_inner)
     }
 
-    async fn handle_response<T>(&self, response: Response) -> Result<T, Error>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let status = response.status();
-        if status.is_success() | status.is_redirection() {
-            match response.json::<T>().await {
-                Ok(data) => Ok(data),
-                // TODO: this should be a serde error
-                Err(err) => Err(Error::HttpRequest(err)),
-            }
-        } else {
-            match status {
-                StatusCode::UNAUTHORIZED => {
-                    let error_msg = response.text().await?;
-                    Err(Error::Unauthorized(error_msg))
-                }
-                StatusCode::BAD_REQUEST => {
-                    let error_msg = response.text().await?;
-                    Err(Error::BadRequest(error_msg))
-                }
-                StatusCode::FORBIDDEN => {
-                    let error_msg = response.text().await?;
-                    Err(Error::Forbidden(error_msg))
-                }
-                StatusCode::UNPROCESSABLE_ENTITY => {
-                    let error_msg = response.text().await?;
-                    Err(Error::UnprocessableEntity(error_msg))
-                }
-                StatusCode::TOO_MANY_REQUESTS => {
-                    let error_msg = response.text().await?;
-                    Err(Error::RateLimited(error_msg))
-                }
-                StatusCode::INTERNAL_SERVER_ERROR => {
-                    let error_msg = response.text().await?;
-                    Err(Error::InternalServerError(error_msg))
-                }
-                StatusCode::BAD_GATEWAY => {
-                    let error_msg = response.text().await?;
-                    Err(Error::BadGateway(error_msg))
-                }
-                StatusCode::SERVICE_UNAVAILABLE => {
-                    let error_msg = response.text().await?;
-                    Err(Error::ServiceUnavailable(error_msg))
-                }
-                status => Err(Error::Unexpected(status)),
-            }
-        }
+    /// List models along with the rate-limit state and request id.
+    pub async fn list_models_with_metadata(
+        &self,
+    ) -> Result<ApiResponse<ListModelsResponse>, Error> {
+        let request = self.transport.request(Method::GET, "models")?;
+        self.transport.send::<ListModelsResponse>(request).await
     }
 }
 
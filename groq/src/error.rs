@@ -1,4 +1,6 @@
 use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -6,34 +8,35 @@ pub enum Error {
     HttpRequest(#[from] reqwest::Error),
 
     #[error("Unauthorized: {0}")]
-    Unauthorized(String),
+    Unauthorized(ApiError),
 
     #[error("Bad Request: {0}")]
-    BadRequest(String),
+    BadRequest(ApiError),
 
     #[error("Not Found: {0}")]
-    NotFound(String),
+    NotFound(ApiError),
 
     #[error("Unprocessable Entity: {0}")]
-    UnprocessableEntity(String),
-
-    #[error("Too Many Requests: {0}")]
-    TooManyRequests(String),
+    UnprocessableEntity(ApiError),
 
     #[error("Internal Server Error: {0}")]
-    InternalServerError(String),
+    InternalServerError(ApiError),
 
     #[error("Forbidden: {0}")]
-    Forbidden(String),
+    Forbidden(ApiError),
 
-    #[error("Rate limited: {0}")]
-    RateLimited(String),
+    #[error("Rate limited: {error}")]
+    RateLimited {
+        error: ApiError,
+        /// Time to wait before the rate limit is reset.
+        reset_after: Option<Duration>,
+    },
 
     #[error("Bad Gateway: {0}")]
-    BadGateway(String),
+    BadGateway(ApiError),
 
     #[error("Service Unavailable: {0}")]
-    ServiceUnavailable(String),
+    ServiceUnavailable(ApiError),
 
     #[error("Missing api key: {0}")]
     MissingApiKey(&'static str),
@@ -44,9 +47,79 @@ pub enum Error {
     #[error("Invalid header value: {0}")]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
 
-    #[error("Deserialization Error: {0}")]
-    Deserialization(String),
+    #[error("Deserialization Error: {source}")]
+    Deserialization {
+        source: serde_json::Error,
+        /// Raw response body that failed to deserialize.
+        body: String,
+    },
+
+    #[error("Unexpected Error: {status} {error}")]
+    Unexpected { status: StatusCode, error: ApiError },
+}
+
+/// Error returned by the Groq API.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct ApiError {
+    /// Human-readable explanation of the error.
+    pub message: String,
+
+    /// Category of the error e.g. **invalid_request_error**.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+
+    /// Machine-readable error code e.g. **invalid_api_key**.
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorResponse {
+    error: ApiError,
+}
+
+impl ApiError {
+    /// Parse an error response body, falling back to the raw body when it doesn't follow Groq's error format.
+    pub fn from_body(body: &str) -> Self {
+        match serde_json::from_str::<ApiErrorResponse>(body) {
+            Ok(response) => response.error,
+            Err(_) => Self {
+                message: body.to_string(),
+                kind: None,
+                code: None,
+            },
+        }
+    }
+}
 
-    #[error("Unexpected Error: {0}")]
-    Unexpected(StatusCode),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_api_error() {
+        let body = r#"{"error":{"message":"Invalid API Key","type":"invalid_request_error","code":"invalid_api_key"}}"#;
+        assert_eq!(
+            ApiError::from_body(body),
+            ApiError {
+                message: "Invalid API Key".to_string(),
+                kind: Some("invalid_request_error".to_string()),
+                code: Some("invalid_api_key".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn should_fallback_to_raw_body() {
+        let body = "<html>502 Bad Gateway</html>";
+        assert_eq!(
+            ApiError::from_body(body),
+            ApiError {
+                message: body.to_string(),
+                kind: None,
+                code: None,
+            }
+        );
+    }
 }
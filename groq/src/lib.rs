@@ -5,3 +5,6 @@ pub mod client;
 pub mod config;
 pub mod error;
 pub mod models;
+mod rate_limit;
+pub mod response;
+mod transport;
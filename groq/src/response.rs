@@ -0,0 +1,77 @@
+use reqwest::header::HeaderMap;
+
+use crate::rate_limit::header_str;
+pub use crate::rate_limit::RateLimit;
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Response body returned alongside the metadata sent in the response headers.
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    /// The deserialized response body.
+    pub data: T,
+
+    /// Metadata extracted from the response headers.
+    pub metadata: ResponseMetadata,
+}
+
+impl<T> ApiResponse<T> {
+    /// Discard the metadata and return the response body.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMetadata {
+    /// Unique identifier of the request. Useful when reaching out to Groq support.
+    pub request_id: Option<String>,
+
+    /// Rate-limit state of the account after the request.
+    pub rate_limit: RateLimit,
+}
+
+impl ResponseMetadata {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            request_id: header_str(headers, REQUEST_ID_HEADER).map(String::from),
+            rate_limit: RateLimit::from_headers(headers),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::{
+        LIMIT_REQUESTS_HEADER, LIMIT_TOKENS_HEADER, REMAINING_REQUESTS_HEADER,
+        REMAINING_TOKENS_HEADER, RESET_REQUESTS_HEADER, RESET_TOKENS_HEADER,
+    };
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderValue;
+    use std::time::Duration;
+
+    #[test]
+    fn should_parse_metadata_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("req_123"));
+        headers.insert(LIMIT_REQUESTS_HEADER, HeaderValue::from_static("14400"));
+        headers.insert(LIMIT_TOKENS_HEADER, HeaderValue::from_static("18000"));
+        headers.insert(REMAINING_REQUESTS_HEADER, HeaderValue::from_static("14370"));
+        headers.insert(REMAINING_TOKENS_HEADER, HeaderValue::from_static("0"));
+        headers.insert(RESET_REQUESTS_HEADER, HeaderValue::from_static("2m59.56s"));
+        headers.insert(RESET_TOKENS_HEADER, HeaderValue::from_static("7.66s"));
+
+        let metadata = ResponseMetadata::from_headers(&headers);
+        assert_eq!(metadata.request_id.as_deref(), Some("req_123"));
+        assert_eq!(metadata.rate_limit.limit_requests, Some(14400));
+        assert_eq!(metadata.rate_limit.limit_tokens, Some(18000));
+        assert_eq!(metadata.rate_limit.remaining_requests, Some(14370));
+        assert_eq!(metadata.rate_limit.remaining_tokens, Some(0));
+        assert_eq!(metadata.rate_limit.retry_after, None);
+        assert_eq!(
+            metadata.rate_limit.reset_after(),
+            Some(Duration::from_secs_f64(7.66))
+        );
+    }
+}
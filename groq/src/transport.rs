@@ -0,0 +1,75 @@
+use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Response, StatusCode, Url};
+
+use crate::{
+    error::{ApiError, Error},
+    response::{ApiResponse, ResponseMetadata},
+};
+
+/// HTTP layer shared by every Groq client.
+#[derive(Debug, Clone)]
+pub(crate) struct Transport {
+    base_url: Url,
+    http_client: ReqwestClient,
+}
+
+impl Transport {
+    pub(crate) fn new(base_url: Url, http_client: ReqwestClient) -> Self {
+        Self {
+            base_url,
+            http_client,
+        }
+    }
+
+    pub(crate) fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
+        let url = self
+            .base_url
+            .join(path)
+            .map_err(|err| Error::UrlParse(err.to_string()))?;
+        Ok(self.http_client.request(method, url))
+    }
+
+    pub(crate) async fn send<T>(&self, request: RequestBuilder) -> Result<ApiResponse<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response = request.send().await?;
+        handle_response(response).await
+    }
+}
+
+async fn handle_response<T>(response: Response) -> Result<ApiResponse<T>, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let status = response.status();
+    let metadata = ResponseMetadata::from_headers(response.headers());
+    let body = response.text().await?;
+
+    if status.is_success() | status.is_redirection() {
+        return match serde_json::from_str::<T>(&body) {
+            Ok(data) => Ok(ApiResponse { data, metadata }),
+            Err(source) => Err(Error::Deserialization { source, body }),
+        };
+    }
+
+    let error = ApiError::from_body(&body);
+    match status {
+        StatusCode::BAD_REQUEST => Err(Error::BadRequest(error)),
+        StatusCode::UNAUTHORIZED => Err(Error::Unauthorized(error)),
+        StatusCode::FORBIDDEN => Err(Error::Forbidden(error)),
+        StatusCode::NOT_FOUND => Err(Error::NotFound(error)),
+        StatusCode::UNPROCESSABLE_ENTITY => Err(Error::UnprocessableEntity(error)),
+        StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited {
+            error,
+            reset_after: metadata.rate_limit.reset_after(),
+        }),
+        StatusCode::INTERNAL_SERVER_ERROR => Err(Error::InternalServerError(error)),
+        StatusCode::BAD_GATEWAY => Err(Error::BadGateway(error)),
+        StatusCode::SERVICE_UNAVAILABLE => Err(Error::ServiceUnavailable(error)),
+        status => Err(Error::Unexpected { status, error }),
+    }
+}
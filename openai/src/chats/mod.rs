@@ -1,7 +1,10 @@
 use message::{ChatCompletion, CreateChatCompletion};
 use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Url};
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    response::{handle_response, ApiResponse},
+};
 
 pub mod message;
 
@@ -22,14 +25,22 @@ impl ChatClient {
         &self,
         payload: CreateChatCompletion,
     ) -> Result<ChatCompletion, Error> {
-        let completion = self
+        self.create_completion_with_metadata(payload)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Create a completion along with the rate-limit state and request id.
+    pub async fn create_completion_with_metadata(
+        &self,
+        payload: CreateChatCompletion,
+    ) -> Result<ApiResponse<ChatCompletion>, Error> {
+        let response = self
             .request(Method::POST, "chat/completions")?
             .json(&payload)
             .send()
-            .await?
-            .json::<ChatCompletion>()
             .await?;
-        Ok(completion)
+        handle_response::<ChatCompletion>(response).await
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
@@ -2,7 +2,10 @@ use core::fmt;
 use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Url};
 use serde::{Deserialize, Serialize};
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    response::{handle_response, ApiResponse},
+};
 
 pub struct EmbeddingClient {
     base_url: Url,
@@ -21,14 +24,22 @@ impl EmbeddingClient {
         &self,
         payload: CreateEmbedding,
     ) -> Result<CreateEmbeddingResponse, Error> {
-        let embedding = self
+        self.create_embedding_with_metadata(payload)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Create an embedding along with the rate-limit state and request id.
+    pub async fn create_embedding_with_metadata(
+        &self,
+        payload: CreateEmbedding,
+    ) -> Result<ApiResponse<CreateEmbeddingResponse>, Error> {
+        let response = self
             .request(Method::POST, "embeddings")?
             .json(&payload)
             .send()
-            .await?
-            .json::<CreateEmbeddingResponse>()
             .await?;
-        Ok(embedding)
+        handle_response::<CreateEmbeddingResponse>(response).await
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
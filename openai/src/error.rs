@@ -1,5 +1,6 @@
+use reqwest::StatusCode;
 use serde::Deserialize;
-use std::str::Utf8Error;
+use std::{str::Utf8Error, time::Duration};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -24,6 +25,40 @@ pub enum Error {
     #[error("Missing API key {0}")]
     MissingApiKey(&'static str),
 
+    #[error("Bad Request: {0}")]
+    BadRequest(ApiError),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(ApiError),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(ApiError),
+
+    #[error("Not Found: {0}")]
+    NotFound(ApiError),
+
+    #[error("Unprocessable Entity: {0}")]
+    UnprocessableEntity(ApiError),
+
+    #[error("Rate limited: {error}")]
+    RateLimited {
+        error: ApiError,
+        /// Time to wait before the rate limit is reset.
+        reset_after: Option<Duration>,
+    },
+
+    #[error("Internal Server Error: {0}")]
+    InternalServerError(ApiError),
+
+    #[error("Bad Gateway: {0}")]
+    BadGateway(ApiError),
+
+    #[error("Service Unavailable: {0}")]
+    ServiceUnavailable(ApiError),
+
+    #[error("Unexpected status: {status} {error}")]
+    UnexpectedStatus { status: StatusCode, error: ApiError },
+
     #[error("Invalid Stream Event")]
     InvalidStreamEvent,
 
@@ -34,6 +69,44 @@ pub enum Error {
     Unexpected(String),
 }
 
+/// Error returned by the OpenAI API.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct ApiError {
+    /// Human-readable explanation of the error.
+    pub message: String,
+
+    /// Category of the error e.g. **invalid_request_error**.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+
+    /// Request parameter the error relates to, if any.
+    pub param: Option<String>,
+
+    /// Machine-readable error code e.g. **invalid_api_key**.
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    error: ApiError,
+}
+
+impl ApiError {
+    /// Parse an error response body, falling back to the raw body when it doesn't follow OpenAI's error format.
+    pub fn from_body(body: &str) -> Self {
+        match serde_json::from_str::<ApiErrorBody>(body) {
+            Ok(body) => body.error,
+            Err(_) => Self {
+                message: body.to_string(),
+                kind: None,
+                param: None,
+                code: None,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
 #[error("Error response: {error_type} {error}")]
 pub struct ApiErrorResponse {
@@ -90,6 +163,23 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn should_parse_api_error() {
+        let body = r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","param":null,"code":"invalid_api_key"}}"#;
+        assert_eq!(
+            ApiError::from_body(body),
+            ApiError {
+                message: "Incorrect API key provided".to_string(),
+                kind: Some("invalid_request_error".to_string()),
+                param: None,
+                code: Some("invalid_api_key".to_string()),
+            }
+        );
+
+        let body = "<html>502 Bad Gateway</html>";
+        assert_eq!(ApiError::from_body(body).message, body);
+    }
+
     #[test]
     fn should_serialize_to_correct_error_values() {
         assert_eq!(
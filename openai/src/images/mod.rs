@@ -2,7 +2,10 @@ use core::fmt;
 use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Url};
 use serde::{Deserialize, Serialize};
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    response::{handle_response, ApiResponse},
+};
 
 pub struct ImageClient {
     base_url: Url,
@@ -19,14 +22,22 @@ impl ImageClient {
 
     /// Creates an image given a prompt.
     pub async fn create_image(&self, payload: CreateImage) -> Result<CreateImageResponse, Error> {
-        let image = self
+        self.create_image_with_metadata(payload)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Creates an image given a prompt along with the rate-limit state and request id.
+    pub async fn create_image_with_metadata(
+        &self,
+        payload: CreateImage,
+    ) -> Result<ApiResponse<CreateImageResponse>, Error> {
+        let response = self
             .request(Method::POST, "images/generations")?
             .json(&payload)
             .send()
-            .await?
-            .json::<CreateImageResponse>()
             .await?;
-        Ok(image)
+        handle_response::<CreateImageResponse>(response).await
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
@@ -147,19 +158,14 @@ impl fmt::Display for ImageModel {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ImageQuality {
     HD,
+    #[default]
     Standard,
 }
 
-impl Default for ImageQuality {
-    fn default() -> Self {
-        Self::Standard
-    }
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateImageResponse {
     pub created: u64,
@@ -171,25 +177,21 @@ pub struct ImageData {
     pub url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum ImageResponseFormat {
+    #[default]
     Url,
     Base64Json,
 }
 
-impl Default for ImageResponseFormat {
-    fn default() -> Self {
-        Self::Url
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub enum ImageSize {
     #[serde(rename = "256x256")]
     S256x256,
     #[serde(rename = "512x512")]
     S512x512,
+    #[default]
     #[serde(rename = "1024x1024")]
     S1024x1024,
     #[serde(rename = "1792x1024")]
@@ -198,12 +200,6 @@ pub enum ImageSize {
     S1024x1792,
 }
 
-impl Default for ImageSize {
-    fn default() -> Self {
-        Self::S1024x1024
-    }
-}
-
 impl fmt::Display for ImageSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -216,15 +212,10 @@ impl fmt::Display for ImageSize {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ImageStyle {
+    #[default]
     Vivid,
     Natural,
 }
-
-impl Default for ImageStyle {
-    fn default() -> Self {
-        Self::Vivid
-    }
-}
@@ -8,3 +8,6 @@ pub mod error;
 pub mod images;
 pub mod models;
 pub mod moderations;
+pub mod pagination;
+mod rate_limit;
+pub mod response;
@@ -1,9 +1,14 @@
 use core::fmt;
+use futures_util::Stream;
 use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Url};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    pagination::{self, Page},
+    response::{handle_response, ApiResponse},
+};
 
 pub mod gpt;
 pub mod o1;
@@ -70,38 +75,66 @@ impl ModelClient {
     }
 
     pub async fn get_model(&self, model_id: impl Into<String>) -> Result<ModelInfo, Error> {
+        self.get_model_with_metadata(model_id)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Get a model along with the rate-limit state and request id.
+    pub async fn get_model_with_metadata(
+        &self,
+        model_id: impl Into<String>,
+    ) -> Result<ApiResponse<ModelInfo>, Error> {
         let path = format!("models/{}", model_id.into());
-        let model = self
-            .request(Method::GET, path.as_str())?
-            .send()
-            .await?
-            .json::<ModelInfo>()
-            .await?;
-        Ok(model)
+        let response = self.request(Method::GET, path.as_str())?.send().await?;
+        handle_response::<ModelInfo>(response).await
     }
 
     pub async fn list_models(&self) -> Result<ListModelsResponse, Error> {
-        let models = self
-            .request(Method::GET, "models")?
-            .send()
-            .await?
-            .json::<ListModelsResponse>()
-            .await?;
-        Ok(models)
+        self.list_models_with_metadata()
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// List models along with the rate-limit state and request id.
+    pub async fn list_models_with_metadata(
+        &self,
+    ) -> Result<ApiResponse<ListModelsResponse>, Error> {
+        let response = self.request(Method::GET, "models")?.send().await?;
+        handle_response::<ListModelsResponse>(response).await
+    }
+
+    /// Stream models, fetching the next pages as needed.
+    pub fn list_models_stream(&self) -> impl Stream<Item = Result<ModelInfo, Error>> + '_ {
+        pagination::paginate(move |after| async move {
+            let mut request = self.request(Method::GET, "models")?;
+            if let Some(after) = after {
+                request = request.query(&[("after", after)]);
+            }
+            let response = request.send().await?;
+            handle_response::<ListModelsResponse>(response)
+                .await
+                .map(ApiResponse::into_inner)
+        })
     }
 
     pub async fn delete_model(
         &self,
         model_id: impl Into<String>,
     ) -> Result<DeleteModelResponse, Error> {
+        self.delete_model_with_metadata(model_id)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Delete a model along with the rate-limit state and request id.
+    pub async fn delete_model_with_metadata(
+        &self,
+        model_id: impl Into<String>,
+    ) -> Result<ApiResponse<DeleteModelResponse>, Error> {
         let path = format!("models/{}", model_id.into());
-        let response = self
-            .request(Method::DELETE, path.as_str())?
-            .send()
-            .await?
-            .json::<DeleteModelResponse>()
-            .await?;
-        Ok(response)
+        let response = self.request(Method::DELETE, path.as_str())?.send().await?;
+        handle_response::<DeleteModelResponse>(response).await
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
@@ -132,6 +165,31 @@ pub struct ModelInfo {
 pub struct ListModelsResponse {
     pub object: String,
     pub data: Vec<ModelInfo>,
+
+    /// Whether more models can be fetched after this page.
+    #[serde(default)]
+    pub has_more: bool,
+
+    /// Id of the last model of this page, used as cursor for the next page.
+    #[serde(default)]
+    pub last_id: Option<String>,
+}
+
+impl Page for ListModelsResponse {
+    type Item = ModelInfo;
+
+    fn next_cursor(&self) -> Option<String> {
+        if !self.has_more {
+            return None;
+        }
+        self.last_id
+            .clone()
+            .or_else(|| self.data.last().map(|model| model.id.clone()))
+    }
+
+    fn into_data(self) -> Vec<Self::Item> {
+        self.data
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
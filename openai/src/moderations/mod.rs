@@ -2,7 +2,10 @@ use core::fmt;
 use reqwest::{Client as ReqwestClient, Method, RequestBuilder, Url};
 use serde::{Deserialize, Serialize};
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    response::{handle_response, ApiResponse},
+};
 
 pub struct ModerationClient {
     base_url: Url,
@@ -18,14 +21,22 @@ impl ModerationClient {
     }
 
     pub async fn create_moderation(&self, payload: CreateModeration) -> Result<Moderation, Error> {
-        let model = self
+        self.create_moderation_with_metadata(payload)
+            .await
+            .map(ApiResponse::into_inner)
+    }
+
+    /// Create a moderation along with the rate-limit state and request id.
+    pub async fn create_moderation_with_metadata(
+        &self,
+        payload: CreateModeration,
+    ) -> Result<ApiResponse<Moderation>, Error> {
+        let response = self
             .request(Method::POST, "moderations")?
             .json(&payload)
             .send()
-            .await?
-            .json::<Moderation>()
             .await?;
-        Ok(model)
+        handle_response::<Moderation>(response).await
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
@@ -0,0 +1,101 @@
+use futures_util::{stream, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+
+use crate::error::Error;
+
+/// A page of results returned by a cursor-paginated list endpoint.
+pub trait Page: DeserializeOwned {
+    type Item;
+
+    /// Cursor to pass as **after** to fetch the next page, if any.
+    fn next_cursor(&self) -> Option<String>;
+
+    /// Consume the page and return its items.
+    fn into_data(self) -> Vec<Self::Item>;
+}
+
+/// Fetch the first page then every following page, yielding their items one by one.
+///
+/// `fetch` receives the cursor of the page to fetch, **None** for the first page. Pages are only fetched once the items of the previous page have been consumed.
+pub(crate) fn paginate<P, F, Fut>(mut fetch: F) -> impl Stream<Item = Result<P::Item, Error>>
+where
+    P: Page,
+    F: FnMut(Option<String>) -> Fut,
+    Fut: Future<Output = Result<P, Error>>,
+{
+    stream::try_unfold(Some(None), move |cursor| {
+        let page = cursor.map(&mut fetch);
+        async move {
+            let Some(page) = page else {
+                return Ok::<_, Error>(None);
+            };
+            let page = page.await?;
+            let next = page.next_cursor().map(Some);
+            let data = stream::iter(page.into_data().into_iter().map(Ok));
+            Ok(Some((data, next)))
+        }
+    })
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Numbers {
+        data: Vec<u32>,
+        has_more: bool,
+    }
+
+    impl Page for Numbers {
+        type Item = u32;
+
+        fn next_cursor(&self) -> Option<String> {
+            self.has_more
+                .then(|| self.data.last().map(u32::to_string))
+                .flatten()
+        }
+
+        fn into_data(self) -> Vec<Self::Item> {
+            self.data
+        }
+    }
+
+    fn page(cursor: Option<String>) -> Result<Numbers, Error> {
+        match cursor.as_deref() {
+            None => Ok(Numbers {
+                data: vec![1, 2],
+                has_more: true,
+            }),
+            Some("2") => Ok(Numbers {
+                data: vec![3],
+                has_more: false,
+            }),
+            Some(cursor) => Err(Error::Unexpected(cursor.to_string())),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_follow_cursors() {
+        let items: Vec<u32> = paginate(|cursor| async move { page(cursor) })
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn should_stop_on_error() {
+        let items: Vec<Result<u32, Error>> =
+            paginate(|_| async move { page(Some("missing".to_string())) })
+                .collect()
+                .await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! Rate-limit headers sent by the OpenAI API.
+
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+const RETRY_AFTER_HEADER: &str = "retry-after";
+pub(crate) const LIMIT_REQUESTS_HEADER: &str = "x-ratelimit-limit-requests";
+pub(crate) const LIMIT_TOKENS_HEADER: &str = "x-ratelimit-limit-tokens";
+pub(crate) const REMAINING_REQUESTS_HEADER: &str = "x-ratelimit-remaining-requests";
+pub(crate) const REMAINING_TOKENS_HEADER: &str = "x-ratelimit-remaining-tokens";
+pub(crate) const RESET_REQUESTS_HEADER: &str = "x-ratelimit-reset-requests";
+pub(crate) const RESET_TOKENS_HEADER: &str = "x-ratelimit-reset-tokens";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Maximum number of requests allowed before the limit is reset.
+    pub limit_requests: Option<u64>,
+
+    /// Maximum number of tokens allowed before the limit is reset.
+    pub limit_tokens: Option<u64>,
+
+    /// Requests left before hitting the limit.
+    pub remaining_requests: Option<u64>,
+
+    /// Tokens left before hitting the limit.
+    pub remaining_tokens: Option<u64>,
+
+    /// Time until the request limit is reset.
+    pub reset_requests: Option<Duration>,
+
+    /// Time until the token limit is reset.
+    pub reset_tokens: Option<Duration>,
+
+    /// Time to wait before retrying. Only sent along with **429 Too Many Requests** responses.
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimit {
+    /// Read the rate-limit headers. Malformed values are ignored.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            limit_requests: header_u64(headers, LIMIT_REQUESTS_HEADER),
+            limit_tokens: header_u64(headers, LIMIT_TOKENS_HEADER),
+            remaining_requests: header_u64(headers, REMAINING_REQUESTS_HEADER),
+            remaining_tokens: header_u64(headers, REMAINING_TOKENS_HEADER),
+            reset_requests: header_str(headers, RESET_REQUESTS_HEADER).and_then(parse_duration),
+            reset_tokens: header_str(headers, RESET_TOKENS_HEADER).and_then(parse_duration),
+            retry_after: header_str(headers, RETRY_AFTER_HEADER)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok()),
+        }
+    }
+
+    /// Time to wait before the next request can succeed.
+    ///
+    /// Uses the **retry-after** header when available. Otherwise, picks the reset time of the exhausted limit, or the longest reset time if none is exhausted.
+    pub fn reset_after(&self) -> Option<Duration> {
+        if self.retry_after.is_some() {
+            return self.retry_after;
+        }
+        match (self.remaining_requests, self.remaining_tokens) {
+            (Some(0), _) if self.reset_requests.is_some() => self.reset_requests,
+            (_, Some(0)) if self.reset_tokens.is_some() => self.reset_tokens,
+            _ => self.reset_requests.max(self.reset_tokens),
+        }
+    }
+}
+
+pub(crate) fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name).and_then(|value| value.to_str().ok())
+}
+
+fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    header_str(headers, name).and_then(|value| value.trim().parse().ok())
+}
+
+/// Parse durations formatted like **2m59.56s**, **7.66s** or **120ms**.
+///
+/// Returns **None** for values that are malformed or don't fit in a [`Duration`].
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut total = 0.0;
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let number_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_end].parse().ok()?;
+        rest = &rest[number_end..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds = match &rest[..unit_end] {
+            "h" => number * 3600.0,
+            "m" => number * 60.0,
+            "s" | "" => number,
+            "ms" => number / 1000.0,
+            _ => return None,
+        };
+        total += seconds;
+        rest = &rest[unit_end..];
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn should_parse_durations() {
+        assert_eq!(parse_duration("7.66s"), Some(Duration::from_secs_f64(7.66)));
+        assert_eq!(
+            parse_duration("2m59.56s"),
+            Some(Duration::from_secs_f64(179.56))
+        );
+        assert_eq!(parse_duration("1h0m1s"), Some(Duration::from_secs(3601)));
+        assert_eq!(parse_duration("120ms"), Some(Duration::from_millis(120)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("soon"), None);
+    }
+
+    #[test]
+    fn should_ignore_invalid_durations() {
+        for value in ["-1", "NaN", "inf", "1e400"] {
+            let mut headers = HeaderMap::new();
+            headers.insert(RETRY_AFTER_HEADER, HeaderValue::from_str(value).unwrap());
+            headers.insert(RESET_TOKENS_HEADER, HeaderValue::from_str(value).unwrap());
+
+            let rate_limit = RateLimit::from_headers(&headers);
+            assert_eq!(rate_limit.retry_after, None, "retry-after: {}", value);
+            assert_eq!(rate_limit.reset_tokens, None, "reset-tokens: {}", value);
+        }
+        assert_eq!(parse_duration(&format!("{}s", "9".repeat(400))), None);
+    }
+
+    #[test]
+    fn should_prefer_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RESET_REQUESTS_HEADER, HeaderValue::from_static("2m59.56s"));
+        headers.insert(RETRY_AFTER_HEADER, HeaderValue::from_static("2"));
+
+        let rate_limit = RateLimit::from_headers(&headers);
+        assert_eq!(rate_limit.reset_after(), Some(Duration::from_secs(2)));
+    }
+}
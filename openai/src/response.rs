@@ -0,0 +1,141 @@
+use reqwest::{header::HeaderMap, Response, StatusCode};
+
+use crate::rate_limit::header_str;
+pub use crate::rate_limit::RateLimit;
+
+use crate::error::{ApiError, Error};
+
+const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Response body returned alongside the metadata sent in the response headers.
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    /// The deserialized response body.
+    pub data: T,
+
+    /// Metadata extracted from the response headers.
+    pub metadata: ResponseMetadata,
+}
+
+impl<T> ApiResponse<T> {
+    /// Discard the metadata and return the response body.
+    pub fn into_inner(self) -> T {
+        self.data
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMetadata {
+    /// Unique identifier of the request. Useful when reaching out to OpenAI support.
+    pub request_id: Option<String>,
+
+    /// Rate-limit state of the account after the request.
+    pub rate_limit: RateLimit,
+}
+
+impl ResponseMetadata {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            request_id: header_str(headers, REQUEST_ID_HEADER).map(String::from),
+            rate_limit: RateLimit::from_headers(headers),
+        }
+    }
+}
+
+pub(crate) async fn handle_response<T>(response: Response) -> Result<ApiResponse<T>, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let status = response.status();
+    let metadata = ResponseMetadata::from_headers(response.headers());
+    let body = response.text().await?;
+
+    if status.is_success() {
+        let data = serde_json::from_str::<T>(&body)?;
+        return Ok(ApiResponse { data, metadata });
+    }
+
+    let error = ApiError::from_body(&body);
+    match status {
+        StatusCode::BAD_REQUEST => Err(Error::BadRequest(error)),
+        StatusCode::UNAUTHORIZED => Err(Error::Unauthorized(error)),
+        StatusCode::FORBIDDEN => Err(Error::Forbidden(error)),
+        StatusCode::NOT_FOUND => Err(Error::NotFound(error)),
+        StatusCode::UNPROCESSABLE_ENTITY => Err(Error::UnprocessableEntity(error)),
+        StatusCode::TOO_MANY_REQUESTS => Err(Error::RateLimited {
+            error,
+            reset_after: metadata.rate_limit.reset_after(),
+        }),
+        StatusCode::INTERNAL_SERVER_ERROR => Err(Error::InternalServerError(error)),
+        StatusCode::BAD_GATEWAY => Err(Error::BadGateway(error)),
+        StatusCode::SERVICE_UNAVAILABLE => Err(Error::ServiceUnavailable(error)),
+        status => Err(Error::UnexpectedStatus { status, error }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::{
+        LIMIT_REQUESTS_HEADER, LIMIT_TOKENS_HEADER, REMAINING_REQUESTS_HEADER,
+        REMAINING_TOKENS_HEADER, RESET_REQUESTS_HEADER, RESET_TOKENS_HEADER,
+    };
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderValue;
+    use std::time::Duration;
+
+    #[test]
+    fn should_parse_metadata_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("req_123"));
+        headers.insert(LIMIT_REQUESTS_HEADER, HeaderValue::from_static("10000"));
+        headers.insert(LIMIT_TOKENS_HEADER, HeaderValue::from_static("200000"));
+        headers.insert(REMAINING_REQUESTS_HEADER, HeaderValue::from_static("9999"));
+        headers.insert(REMAINING_TOKENS_HEADER, HeaderValue::from_static("0"));
+        headers.insert(RESET_REQUESTS_HEADER, HeaderValue::from_static("2m59.56s"));
+        headers.insert(RESET_TOKENS_HEADER, HeaderValue::from_static("7.66s"));
+
+        let metadata = ResponseMetadata::from_headers(&headers);
+        assert_eq!(metadata.request_id.as_deref(), Some("req_123"));
+        assert_eq!(metadata.rate_limit.limit_requests, Some(10000));
+        assert_eq!(metadata.rate_limit.limit_tokens, Some(200000));
+        assert_eq!(metadata.rate_limit.remaining_requests, Some(9999));
+        assert_eq!(metadata.rate_limit.remaining_tokens, Some(0));
+        assert_eq!(metadata.rate_limit.retry_after, None);
+        assert_eq!(
+            metadata.rate_limit.reset_after(),
+            Some(Duration::from_secs_f64(7.66))
+        );
+    }
+
+    fn response(status: u16, body: &'static str) -> Response {
+        http::Response::builder()
+            .status(status)
+            .header("retry-after", "2")
+            .body(body)
+            .unwrap()
+            .into()
+    }
+
+    #[tokio::test]
+    async fn should_return_api_errors() {
+        let body = r#"{"error":{"message":"The model does not exist","type":"invalid_request_error","param":"model","code":"model_not_found"}}"#;
+        match handle_response::<serde_json::Value>(response(404, body)).await {
+            Err(Error::NotFound(error)) => {
+                assert_eq!(error.message, "The model does not exist");
+                assert_eq!(error.code.as_deref(), Some("model_not_found"));
+            }
+            other => panic!("Expected 'NotFound' error, got {:?}", other),
+        }
+
+        match handle_response::<serde_json::Value>(response(429, body)).await {
+            Err(Error::RateLimited { reset_after, .. }) => {
+                assert_eq!(reset_after, Some(Duration::from_secs(2)))
+            }
+            other => panic!("Expected 'RateLimited' error, got {:?}", other),
+        }
+
+        let result = handle_response::<serde_json::Value>(response(200, r#"{"ok":true}"#)).await;
+        assert_eq!(result.unwrap().data, serde_json::json!({ "ok": true }));
+    }
+}
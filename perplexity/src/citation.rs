@@ -0,0 +1,92 @@
+use std::ops::Range;
+
+/// A **[n]** citation marker found in the text generated by the model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CitationMarker<'a> {
+    /// The number written in the marker. Citations are numbered from **1**.
+    pub number: usize,
+
+    /// Byte range of the marker, brackets included, in the text.
+    pub range: Range<usize>,
+
+    /// The cited URL, if the number matches one of the returned citations.
+    pub url: Option<&'a str>,
+}
+
+/// Find the **[n]** citation markers in `text` and map them to the URLs in `citations`.
+///
+/// Markers are numbered from **1** so **[1]** refers to the first citation.
+pub fn resolve_citations<'a>(text: &str, citations: &'a [String]) -> Vec<CitationMarker<'a>> {
+    let mut markers = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find('[').map(|start| start + offset) {
+        let digits = text[start + 1..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len() - start - 1);
+        let end = start + 1 + digits;
+        if digits == 0 || !text[end..].starts_with(']') {
+            offset = start + 1;
+            continue;
+        }
+        if let Ok(number) = text[start + 1..end].parse::<usize>() {
+            markers.push(CitationMarker {
+                number,
+                range: start..end + 1,
+                url: number
+                    .checked_sub(1)
+                    .and_then(|index| citations.get(index))
+                    .map(String::as_str),
+            });
+        }
+        offset = end + 1;
+    }
+    markers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_resolve_citation_markers() {
+        let citations = vec![
+            "https://example.com/a".to_string(),
+            "https://example.com/b".to_string(),
+        ];
+        let text = "Bangkok is hot[1][2], very hot[3].";
+
+        assert_eq!(
+            resolve_citations(text, &citations),
+            vec![
+                CitationMarker {
+                    number: 1,
+                    range: 14..17,
+                    url: Some("https://example.com/a"),
+                },
+                CitationMarker {
+                    number: 2,
+                    range: 17..20,
+                    url: Some("https://example.com/b"),
+                },
+                CitationMarker {
+                    number: 3,
+                    range: 30..33,
+                    url: None,
+                },
+            ]
+        );
+        assert_eq!(&text[14..17], "[1]");
+    }
+
+    #[test]
+    fn should_ignore_non_numeric_brackets() {
+        let citations = vec!["https://example.com".to_string()];
+        let text = "An [empty] list [] or [x1] or [0]";
+
+        let markers = resolve_citations(text, &citations);
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].number, 0);
+        assert_eq!(markers[0].url, None);
+    }
+}
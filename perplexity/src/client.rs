@@ -1,10 +1,16 @@
+use futures_util::{future, stream, Stream, StreamExt};
 use reqwest::{
-    header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
-    Client as ReqwestClient, Method, RequestBuilder, Url,
+    header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE},
+    Client as ReqwestClient, Method, RequestBuilder, Response, Url,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{config::Config, error::Error};
+use crate::{
+    citation::{resolve_citations, CitationMarker},
+    config::Config,
+    error::Error,
+    stream::{ChatCompletionChunk, EventParser},
+};
 
 pub struct Client {
     base_url: Url,
@@ -37,14 +43,47 @@ impl Client {
         &self,
         payload: CreateChatCompletion,
     ) -> Result<ChatCompletion, Error> {
-        let completion = self
+        let response = self
             .request(Method::POST, "chat/completions")?
             .json(&payload)
             .send()
-            .await?
-            .json::<ChatCompletion>()
             .await?;
-        Ok(completion)
+        self.handle_response::<ChatCompletion>(response).await
+    }
+
+    /// Stream a completion with server-sent events.
+    ///
+    /// The **stream** option of the payload is forced to **true**.
+    pub async fn stream_completion(
+        &self,
+        payload: CreateChatCompletion,
+    ) -> Result<impl Stream<Item = Result<ChatCompletionChunk, Error>>, Error> {
+        let response = self
+            .request(Method::POST, "chat/completions")?
+            .header(ACCEPT, "text/event-stream")
+            .json(&payload.with_stream(true))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await?;
+            return Err(Error::from_status(status, &body));
+        }
+
+        Ok(response
+            .bytes_stream()
+            .map(Some)
+            .chain(stream::once(future::ready(None)))
+            .scan(EventParser::default(), |parser, chunk| {
+                let events = match chunk {
+                    Some(Ok(bytes)) => parser.push(&bytes),
+                    Some(Err(err)) => vec![Err(Error::from(err))],
+                    None => parser.finish(),
+                };
+                future::ready(Some(stream::iter(events)))
+            })
+            .flatten())
     }
 
     fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
@@ -54,6 +93,19 @@ impl Client {
             .map_err(|err| Error::UrlParse(err.to_string()))?;
         Ok(self.http_client.request(method, url))
     }
+
+    async fn handle_response<T>(&self, response: Response) -> Result<T, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let status = response.status();
+        let body = response.text().await?;
+        if status.is_success() {
+            Ok(serde_json::from_str::<T>(&body)?)
+        } else {
+            Err(Error::from_status(status, &body))
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -75,6 +127,47 @@ pub struct ChatCompletion {
 
     /// Usage statistics for the completion request.
     pub usage: CompletionUsage,
+
+    /// The URLs cited by the model. Only returned when **return_citations** is enabled on an online model.
+    pub citations: Option<Vec<String>>,
+
+    /// The images related to the answer. Only returned when **return_images** is enabled on an online model.
+    pub images: Option<Vec<Image>>,
+
+    /// Follow-up questions related to the answer. Only returned when **return_related_questions** is enabled on an online model.
+    pub related_questions: Option<Vec<String>>,
+}
+
+impl ChatCompletion {
+    /// The content of the first choice.
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .map(|choice| choice.message.content.as_str())
+    }
+
+    /// Map the **[n]** citation markers of the first choice to the cited URLs.
+    pub fn citation_markers(&self) -> Vec<CitationMarker<'_>> {
+        match (self.content(), &self.citations) {
+            (Some(content), Some(citations)) => resolve_citations(content, citations),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Image {
+    /// The URL of the image.
+    pub image_url: String,
+
+    /// The URL of the page the image was found on.
+    pub origin_url: Option<String>,
+
+    /// The height of the image in pixels.
+    pub height: Option<u32>,
+
+    /// The width of the image in pixels.
+    pub width: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -112,6 +205,7 @@ pub struct Message {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CompletionDelta {
     /// The contents of the message in this turn of conversation.
+    #[serde(default)]
     pub content: String,
 
     /// The role of the speaker in this turn of conversation. After the (optional) system message, user and assistant roles should alternate with user then assistant, ending in user.
@@ -330,17 +424,71 @@ pub enum Model {
     Llama31InstructLarge,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum RecencyFilter {
     Hour,
     Day,
     Week,
+    #[default]
     Month,
 }
 
-impl Default for RecencyFilter {
-    fn default() -> Self {
-        Self::Month
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_deserialize_completion_with_citations() {
+        let raw = r#"{
+            "id": "c6a1",
+            "model": "llama-3.1-sonar-small-128k-online",
+            "object": "chat.completion",
+            "created": 1726000000,
+            "citations": ["https://example.com/a", "https://example.com/b"],
+            "images": [{"image_url": "https://example.com/a.png", "origin_url": "https://example.com/a", "height": 600, "width": 800}],
+            "related_questions": ["Where to eat in Bangkok?"],
+            "choices": [{
+                "index": 0,
+                "finish_reason": "stop",
+                "message": {"role": "assistant", "content": "Try Thipsamai[2]."},
+                "delta": {"role": "assistant", "content": ""}
+            }],
+            "usage": {"prompt_tokens": 8, "completion_tokens": 5, "total_tokens": 13}
+        }"#;
+        let completion: ChatCompletion = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(completion.content(), Some("Try Thipsamai[2]."));
+        assert_eq!(completion.images.as_ref().unwrap()[0].width, Some(800));
+        assert_eq!(
+            completion.related_questions,
+            Some(vec!["Where to eat in Bangkok?".to_string()])
+        );
+
+        let markers = completion.citation_markers();
+        assert_eq!(markers.len(), 1);
+        assert_eq!(markers[0].url, Some("https://example.com/b"));
+    }
+
+    #[test]
+    fn should_deserialize_completion_without_citations() {
+        let raw = r#"{
+            "id": "c6a1",
+            "model": "llama-3.1-8b-instruct",
+            "object": "chat.completion",
+            "created": 1726000000,
+            "choices": [{
+                "index": 0,
+                "finish_reason": "stop",
+                "message": {"role": "assistant", "content": "Hello[1]"},
+                "delta": {"role": "assistant", "content": ""}
+            }],
+            "usage": {"prompt_tokens": 8, "completion_tokens": 5, "total_tokens": 13}
+        }"#;
+        let completion: ChatCompletion = serde_json::from_str(raw).unwrap();
+
+        assert!(completion.citations.is_none());
+        assert!(completion.citation_markers().is_empty());
     }
 }
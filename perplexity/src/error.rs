@@ -1,10 +1,23 @@
+use reqwest::StatusCode;
 use serde::Deserialize;
 use std::str::Utf8Error;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("API error: {0}")]
-    Api(ApiErrorResponse),
+    #[error("Bad request: {0}")]
+    BadRequest(ApiError),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(ApiError),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(ApiError),
+
+    #[error("Server error: {status} {error}")]
+    ServerError { status: StatusCode, error: ApiError },
+
+    #[error("Unexpected status code: {status} {error}")]
+    UnexpectedStatus { status: StatusCode, error: ApiError },
 
     #[error("HTTP client error: {0}")]
     Network(#[from] reqwest::Error),
@@ -34,55 +47,63 @@ pub enum Error {
     Unexpected(String),
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
-#[error("Error response: {error_type} {error}")]
-pub struct ApiErrorResponse {
-    #[serde(rename = "type")]
-    pub error_type: String,
-    pub error: ApiErrorDetail,
+impl Error {
+    /// Map an unsuccessful response to the matching error variant.
+    pub(crate) fn from_status(status: StatusCode, body: &str) -> Self {
+        let error = ApiError::from_body(body);
+        match status {
+            StatusCode::BAD_REQUEST => Self::BadRequest(error),
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(error),
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited(error),
+            status if status.is_server_error() => Self::ServerError { status, error },
+            status => Self::UnexpectedStatus { status, error },
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
-#[error("Api error: {error_type} {message}")]
-pub struct ApiErrorDetail {
-    #[serde(rename = "type")]
-    pub error_type: ApiErrorType,
+/// Error returned by the Perplexity API.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct ApiError {
+    /// Human-readable explanation of the error.
     pub message: String,
-}
-
-#[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
-pub enum ApiErrorType {
-    #[error("invalid_request_error")]
-    #[serde(rename = "invalid_request_error")]
-    InvalidRequest,
-
-    #[error("authentication_error")]
-    #[serde(rename = "authentication_error")]
-    Authentication,
-
-    #[error("permission_error")]
-    #[serde(rename = "permission_error")]
-    Permission,
 
-    #[error("not_found_error")]
-    #[serde(rename = "not_found_error")]
-    NotFound,
-
-    #[error("request_too_large")]
-    #[serde(rename = "request_too_large")]
-    RequestTooLarge,
+    /// Category of the error e.g. **invalid_model**.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
 
-    #[error("rate_limit_error")]
-    #[serde(rename = "rate_limit_error")]
-    RateLimit,
+    /// HTTP status code echoed by the API.
+    pub code: Option<u16>,
+}
 
-    #[error("api_error")]
-    #[serde(rename = "api_error")]
-    Unexpected,
+#[derive(Debug, Deserialize)]
+struct ApiErrorResponse {
+    error: ApiError,
+}
 
-    #[error("overloaded_error")]
-    #[serde(rename = "overloaded_error")]
-    Overloaded,
+impl ApiError {
+    /// Parse an error response body.
+    ///
+    /// Some errors, like authentication failures, are returned as HTML pages by the gateway. In that case the page title is used as message.
+    pub fn from_body(body: &str) -> Self {
+        if let Ok(response) = serde_json::from_str::<ApiErrorResponse>(body) {
+            return response.error;
+        }
+        let message = body
+            .find("<title>")
+            .and_then(|start| {
+                let title = &body[start + "<title>".len()..];
+                title.find("</title>").map(|end| &title[..end])
+            })
+            .unwrap_or(body)
+            .trim()
+            .to_string();
+        Self {
+            message,
+            kind: None,
+            code: None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -91,23 +112,52 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[test]
-    fn should_serialize_to_correct_error_values() {
-        assert_eq!(
-            &ApiErrorType::InvalidRequest.to_string(),
-            "invalid_request_error"
-        );
-        assert_eq!(
-            &ApiErrorType::Authentication.to_string(),
-            "authentication_error"
-        );
-        assert_eq!(&ApiErrorType::Permission.to_string(), "permission_error");
-        assert_eq!(&ApiErrorType::NotFound.to_string(), "not_found_error");
-        assert_eq!(
-            &ApiErrorType::RequestTooLarge.to_string(),
-            "request_too_large"
-        );
-        assert_eq!(&ApiErrorType::RateLimit.to_string(), "rate_limit_error");
-        assert_eq!(&ApiErrorType::Unexpected.to_string(), "api_error");
-        assert_eq!(&ApiErrorType::Overloaded.to_string(), "overloaded_error");
+    fn should_parse_json_error() {
+        let body =
+            r#"{"error":{"message":"Invalid model 'gpt-4'.","type":"invalid_model","code":400}}"#;
+        let error = Error::from_status(StatusCode::BAD_REQUEST, body);
+
+        if let Error::BadRequest(error) = error {
+            assert_eq!(error.message, "Invalid model 'gpt-4'.");
+            assert_eq!(error.kind.as_deref(), Some("invalid_model"));
+            assert_eq!(error.code, Some(400));
+        } else {
+            panic!("Expected 'BadRequest' error");
+        }
+    }
+
+    #[test]
+    fn should_parse_html_error() {
+        let body = "<html>\r\n<head><title>401 Authorization Required</title></head>\r\n<body><center><h1>401 Authorization Required</h1></center></body>\r\n</html>";
+        let error = Error::from_status(StatusCode::UNAUTHORIZED, body);
+
+        if let Error::Unauthorized(error) = error {
+            assert_eq!(error.message, "401 Authorization Required");
+            assert_eq!(error.kind, None);
+        } else {
+            panic!("Expected 'Unauthorized' error");
+        }
+    }
+
+    #[test]
+    fn should_map_status_codes() {
+        assert!(matches!(
+            Error::from_status(StatusCode::TOO_MANY_REQUESTS, "slow down"),
+            Error::RateLimited(_)
+        ));
+        assert!(matches!(
+            Error::from_status(StatusCode::BAD_GATEWAY, "oops"),
+            Error::ServerError {
+                status: StatusCode::BAD_GATEWAY,
+                ..
+            }
+        ));
+        assert!(matches!(
+            Error::from_status(StatusCode::NOT_FOUND, "missing"),
+            Error::UnexpectedStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ));
     }
 }
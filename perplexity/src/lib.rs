@@ -1,5 +1,7 @@
 #![doc = include_str!("../README.md")]
 
+pub mod citation;
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod stream;
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{CompletionDelta, CompletionUsage, FinishReason, Image, Model},
+    error::Error,
+};
+
+const DONE_EVENT: &str = "[DONE]";
+
+/// Chunk of a chat completion streamed with server-sent events.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    /// An ID generated uniquely for each response. Shared by every chunk of the same completion.
+    pub id: String,
+
+    /// The model used to generate the response.
+    pub model: Model,
+
+    /// The object type, which always equals **chat.completion**.
+    pub object: String,
+
+    /// The Unix timestamp (in seconds) of when the completion was created.
+    pub created: u64,
+
+    /// The incremental choices generated since the previous chunk.
+    pub choices: Vec<ChunkChoice>,
+
+    /// The URLs cited by the model. Only returned by online models.
+    pub citations: Option<Vec<String>>,
+
+    /// The images related to the answer. Only returned when **return_images** is enabled.
+    pub images: Option<Vec<Image>>,
+
+    /// Follow-up questions related to the answer. Only returned when **return_related_questions** is enabled.
+    pub related_questions: Option<Vec<String>>,
+
+    /// Usage statistics for the completion request so far.
+    pub usage: Option<CompletionUsage>,
+}
+
+impl ChatCompletionChunk {
+    /// The text generated since the previous chunk for the first choice.
+    pub fn content(&self) -> Option<&str> {
+        self.choices
+            .first()
+            .map(|choice| choice.delta.content.as_str())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChunkChoice {
+    pub index: u64,
+
+    /// The reason the model stopped generating tokens. Only set on the last chunk.
+    pub finish_reason: Option<FinishReason>,
+
+    /// The incrementally streamed next tokens.
+    pub delta: CompletionDelta,
+}
+
+/// Incremental parser turning raw server-sent events bytes into completion chunks.
+///
+/// Events can be split across several network chunks so incomplete events are buffered until their terminating blank line is received. The **\r\n** and **\r** line endings are normalized to **\n**.
+#[derive(Debug, Default)]
+pub(crate) struct EventParser {
+    buffer: Vec<u8>,
+
+    /// Whether the last byte received is a **\r**, so that a following **\n** belongs to the same line ending.
+    after_cr: bool,
+}
+
+impl EventParser {
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<Result<ChatCompletionChunk, Error>> {
+        for &byte in bytes {
+            match byte {
+                b'\r' => {
+                    self.buffer.push(b'\n');
+                    self.after_cr = true;
+                }
+                b'\n' if self.after_cr => self.after_cr = false,
+                _ => {
+                    self.buffer.push(byte);
+                    self.after_cr = false;
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        while let Some(position) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+            let event: Vec<u8> = self.buffer.drain(..position + 2).collect();
+            if let Some(event) = Self::parse_event(&event[..position]) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Parse the last event when the stream ends without a blank line.
+    pub(crate) fn finish(&mut self) -> Vec<Result<ChatCompletionChunk, Error>> {
+        let event = std::mem::take(&mut self.buffer);
+        Self::parse_event(&event).into_iter().collect()
+    }
+
+    fn parse_event(event: &[u8]) -> Option<Result<ChatCompletionChunk, Error>> {
+        let event = match std::str::from_utf8(event) {
+            Ok(event) => event,
+            Err(err) => return Some(Err(Error::Utf8Error(err))),
+        };
+        let data = event
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        if data.is_empty() || data == DONE_EVENT {
+            return None;
+        }
+        Some(serde_json::from_str::<ChatCompletionChunk>(&data).map_err(Error::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const CHUNK: &str = r#"{"id": "c6a1", "model": "llama-3.1-sonar-small-128k-online", "created": 1726000000, "usage": {"prompt_tokens": 8, "completion_tokens": 2, "total_tokens": 10}, "citations": ["https://example.com"], "object": "chat.completion", "choices": [{"index": 0, "finish_reason": null, "message": {"role": "assistant", "content": "Hello"}, "delta": {"role": "assistant", "content": "Hello"}}]}"#;
+
+    #[test]
+    fn should_parse_complete_events() {
+        let mut parser = EventParser::default();
+        let raw = format!("data: {}\r\n\r\ndata: {}\r\n\r\n", CHUNK, CHUNK);
+
+        let events = parser.push(raw.as_bytes());
+        assert_eq!(events.len(), 2);
+
+        let chunk = events.into_iter().next().unwrap().unwrap();
+        assert_eq!(chunk.id, "c6a1");
+        assert_eq!(chunk.content(), Some("Hello"));
+        assert_eq!(
+            chunk.citations,
+            Some(vec!["https://example.com".to_string()])
+        );
+        assert!(chunk.choices[0].finish_reason.is_none());
+    }
+
+    #[test]
+    fn should_buffer_events_split_across_chunks() {
+        let mut parser = EventParser::default();
+        let raw = format!("data: {}\n\n", CHUNK);
+        let (head, tail) = raw.split_at(42);
+
+        assert!(parser.push(head.as_bytes()).is_empty());
+
+        let events = parser.push(tail.as_bytes());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_ref().unwrap().content(), Some("Hello"));
+    }
+
+    #[test]
+    fn should_skip_done_event() {
+        let mut parser = EventParser::default();
+        assert!(parser.push(b"data: [DONE]\n\n").is_empty());
+    }
+
+    #[test]
+    fn should_return_error_for_invalid_event() {
+        let mut parser = EventParser::default();
+        let events = parser.push(b"data: {\"id\": 1}\n\n");
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], Err(Error::JsonDeserialize(_))));
+    }
+
+    #[test]
+    fn should_parse_trailing_event_on_finish() {
+        let mut parser = EventParser::default();
+        let raw = format!("data: {}\n\ndata: {}", CHUNK, CHUNK);
+
+        assert_eq!(parser.push(raw.as_bytes()).len(), 1);
+        let events = parser.finish();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_ref().unwrap().content(), Some("Hello"));
+        assert!(parser.finish().is_empty());
+    }
+
+    #[test]
+    fn should_normalize_line_endings() {
+        let mut parser = EventParser::default();
+        let raw = format!("data: {}\r\rdata: {}\r", CHUNK, CHUNK);
+        let (head, tail) = raw.split_at(raw.len() - 1);
+
+        assert_eq!(parser.push(head.as_bytes()).len(), 1);
+        assert_eq!(parser.push(tail.as_bytes()).len(), 0);
+        assert_eq!(parser.push(b"\n\r\n").len(), 1);
+    }
+}
@@ -1,15 +1,45 @@
+use futures_util::{future, stream, Stream, StreamExt};
+use reqwest::multipart::{Form, Part};
 use reqwest::{
-    header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE},
+    header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE},
     Method, RequestBuilder, Response, StatusCode, Url,
 };
 use serde::{Deserialize, Serialize};
+use std::{
+    path::Path,
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+use tokio::io::AsyncWriteExt;
+
+use crate::{
+    config::Config,
+    error::{ApiError, Error},
+    files::{self, File, FileContent, FileEncoding, FileInput, ListFiles, MAX_DATA_URL_SIZE},
+    pagination::{self, impl_page, Page},
+    reference::ModelRef,
+    schema::InputSchema,
+    stream::{EventParser, PredictionEvent},
+    timestamp::Timestamp,
+    wait::WaitOptions,
+};
+
+const PREFER_HEADER: &str = "Prefer";
+
+/// Time given to an idle deployment to be deleted when the wait options have no timeout. The API only deletes deployments offline for at least 15 minutes.
+const DEFAULT_DELETE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
 
-use crate::{config::Config, error::Error};
+/// Details of the problems returned when deleting a deployment that isn't idle yet.
+const DEPLOYMENT_IN_USE_DETAILS: &[&str] = &[
+    "can only be deleted if they have been offline and unused",
+    "has running instances",
+];
 
 pub struct Client {
     api_key: String,
     base_url: Url,
     http_client: reqwest::Client,
+    download_client: reqwest::Client,
 }
 
 impl Client {
@@ -32,6 +62,7 @@ impl Client {
             api_key: config.api_key,
             base_url,
             http_client,
+            download_client: reqwest::Client::new(),
         })
     }
 
@@ -57,6 +88,11 @@ impl Client {
         self.handle_response::<ListCollections>(response).await
     }
 
+    /// Stream collections of models, fetching the next pages as needed.
+    pub fn collections_stream(&self) -> impl Stream<Item = Result<Collection, Error>> + '_ {
+        self.paginate::<ListCollections>("collections")
+    }
+
     /// List collection of models.
     pub async fn collection_models(
         &self,
@@ -80,6 +116,11 @@ impl Client {
         self.handle_response::<ListDeployments>(response).await
     }
 
+    /// Stream deployments, fetching the next pages as needed.
+    pub fn deployments_stream(&self) -> impl Stream<Item = Result<Deployment, Error>> + '_ {
+        self.paginate::<ListDeployments>("deployments")
+    }
+
     /// Create a new deployment.
     pub async fn create_deployment(&self, payload: CreateDeployment) -> Result<Deployment, Error> {
         let response = self
@@ -90,13 +131,16 @@ impl Client {
         self.handle_response::<Deployment>(response).await
     }
 
-    /// Update a deployment.
+    /// Update a deployment. Only the fields set in the payload are changed.
+    ///
+    /// Changing the configuration creates a new release of the deployment.
     pub async fn update_deployment(
         &self,
-        owner: String,
+        owner: impl Into<String>,
+        name: impl Into<String>,
         payload: UpdateDeployment,
     ) -> Result<Deployment, Error> {
-        let path = format!("deployments/{}/{}", owner, payload.name);
+        let path = format!("deployments/{}/{}", owner.into(), name.into());
         let response = self
             .request(Method::PATCH, path.as_str())?
             .json(&payload)
@@ -105,14 +149,108 @@ impl Client {
         self.handle_response::<Deployment>(response).await
     }
 
+    /// Change the number of instances of a deployment.
+    pub async fn scale_deployment(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+        min_instances: u16,
+        max_instances: u16,
+    ) -> Result<Deployment, Error> {
+        let payload = UpdateDeployment::new()
+            .with_min_instances(min_instances)
+            .with_max_instances(max_instances);
+        self.update_deployment(owner, name, payload).await
+    }
+
+    /// Let a deployment shut down all its instances once idle by setting its minimum instances to zero.
+    ///
+    /// Returns the configuration before scaling, to restore it later with [`Client::scale_deployment`].
+    pub async fn scale_deployment_to_zero(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Result<DeploymentConfiguration, Error> {
+        let (owner, name) = (owner.into(), name.into());
+        let previous = self.deployment(owner.clone(), name.clone()).await?;
+        let payload = UpdateDeployment::new().with_min_instances(0);
+        self.update_deployment(owner, name, payload).await?;
+        Ok(previous.current_release.configuration)
+    }
+
+    /// Poll a deployment until `is_ready` returns true, e.g. until a new release is live.
+    ///
+    /// Returns an error if the deployment isn't ready before the timeout.
+    pub async fn wait_until_ready(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+        options: &WaitOptions,
+        is_ready: impl Fn(&Deployment) -> bool,
+    ) -> Result<Deployment, Error> {
+        let (owner, name) = (owner.into(), name.into());
+        let deployment = self.deployment(owner.clone(), name.clone()).await?;
+        options
+            .poll(
+                deployment,
+                || self.deployment(owner.clone(), name.clone()),
+                is_ready,
+            )
+            .await
+    }
+
     /// Delete a deployment.
     ///
     /// Deployment deletion has some restrictions:
     ///     - You can only delete deployments that have been offline and unused for at least 15 minutes.
-    pub async fn delete_deployment(&self, owner: String, name: String) -> Result<(), Error> {
-        let path = format!("deployments/{}/{}", owner, name);
+    pub async fn delete_deployment(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Result<(), Error> {
+        let path = format!("deployments/{}/{}", owner.into(), name.into());
         let response = self.request(Method::DELETE, path.as_str())?.send().await?;
-        self.handle_response(response).await
+        self.handle_empty_response(response).await
+    }
+
+    /// Scale a deployment to zero then delete it as soon as it is idle.
+    ///
+    /// Deletion is retried according to the options while the API rejects it because the deployment is still in use. Other errors are returned right away.
+    ///
+    /// Gives up after the timeout of the options, or **30 minutes** when unset, returning the last error of the API.
+    pub async fn delete_deployment_when_idle(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+        options: &WaitOptions,
+    ) -> Result<(), Error> {
+        let (owner, name) = (owner.into(), name.into());
+        self.scale_deployment_to_zero(owner.clone(), name.clone())
+            .await?;
+        let options = match options.timeout {
+            Some(_) => options.clone(),
+            None => options.clone().with_timeout(DEFAULT_DELETE_TIMEOUT),
+        };
+        let last_error = Mutex::new(None);
+        let try_delete = || async {
+            match self.delete_deployment(owner.clone(), name.clone()).await {
+                Ok(()) => Ok(true),
+                Err(err) if is_deployment_in_use(&err) => {
+                    *last_error.lock().unwrap_or_else(PoisonError::into_inner) = Some(err);
+                    Ok(false)
+                }
+                Err(err) => Err(err),
+            }
+        };
+        let deleted = try_delete().await?;
+        match options.poll(deleted, try_delete, |deleted| *deleted).await {
+            Ok(_) => Ok(()),
+            Err(Error::Timeout(timeout)) => Err(last_error
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner)
+                .unwrap_or(Error::Timeout(timeout))),
+            Err(err) => Err(err),
+        }
     }
 
     /// Get a prediction.
@@ -128,30 +266,273 @@ impl Client {
         self.handle_response::<ListPredictions>(response).await
     }
 
+    /// Stream predictions, fetching the next pages as needed.
+    pub fn predictions_stream(&self) -> impl Stream<Item = Result<Prediction, Error>> + '_ {
+        self.paginate::<ListPredictions>("predictions")
+    }
+
     /// Create a prediction.
     pub async fn create_prediction(&self, payload: CreatePrediction) -> Result<Prediction, Error> {
+        let response = self.prediction_request(payload).await?.send().await?;
+        self.handle_response::<Prediction>(response).await
+    }
+
+    /// Create a prediction and wait for it to complete.
+    ///
+    /// Returns an error if the prediction fails, is canceled or doesn't complete before the timeout.
+    pub async fn run(
+        &self,
+        payload: CreatePrediction,
+        options: &WaitOptions,
+    ) -> Result<Prediction, Error> {
+        let request = self.prediction_request(payload).await?;
+        self.run_request(request, options).await
+    }
+
+    /// Check the payload against the input schema of `version`, e.g. fetched with [`Client::model_version`], then create the prediction.
+    ///
+    /// Invalid input is rejected with [`Error::InvalidInput`] before any request is sent.
+    pub async fn create_prediction_validated(
+        &self,
+        payload: CreatePrediction,
+        version: &ModelVersion,
+    ) -> Result<Prediction, Error> {
+        payload.validate(version)?;
+        self.create_prediction(payload).await
+    }
+
+    /// Check the payload against the input schema of `version` then create the prediction and wait for it to complete.
+    ///
+    /// Invalid input is rejected with [`Error::InvalidInput`] before any request is sent.
+    pub async fn run_validated(
+        &self,
+        payload: CreatePrediction,
+        version: &ModelVersion,
+        options: &WaitOptions,
+    ) -> Result<Prediction, Error> {
+        payload.validate(version)?;
+        self.run(payload, options).await
+    }
+
+    /// Poll a prediction until it reaches a terminal status.
+    ///
+    /// Returns an error if the prediction fails, is canceled or doesn't complete before the timeout.
+    pub async fn wait_for_prediction(
+        &self,
+        prediction: Prediction,
+        options: &WaitOptions,
+    ) -> Result<Prediction, Error> {
+        let id = prediction.id.clone();
+        let prediction = options
+            .poll(
+                prediction,
+                || self.prediction(id.clone()),
+                |prediction| prediction.status.is_terminal(),
+            )
+            .await?;
+        prediction.into_result()
+    }
+
+    /// Create a streaming prediction and consume its events.
+    ///
+    /// The **stream** option of the payload is forced to **true**.
+    pub async fn stream(
+        &self,
+        payload: CreatePrediction,
+    ) -> Result<impl Stream<Item = Result<PredictionEvent, Error>>, Error> {
+        let request = self.prediction_request(payload.with_stream(true)).await?;
+        self.stream_request(request).await
+    }
+
+    /// Consume the events of a prediction created with **stream = true**.
+    pub async fn stream_prediction(
+        &self,
+        prediction: &Prediction,
+    ) -> Result<impl Stream<Item = Result<PredictionEvent, Error>>, Error> {
+        let url = prediction
+            .urls
+            .stream
+            .as_deref()
+            .ok_or_else(|| Error::StreamUnavailable(prediction.id.clone()))?;
         let response = self
-            .request(Method::POST, "predictions")?
-            .json(&payload)
+            .download_request(url)?
+            .header(ACCEPT, "text/event-stream")
+            .header(CACHE_CONTROL, "no-store")
             .send()
             .await?;
-        self.handle_response::<Prediction>(response).await
+
+        if !response.status().is_success() {
+            return Err(self.handle_error(response).await);
+        }
+
+        Ok(response
+            .bytes_stream()
+            .map(Some)
+            .chain(stream::once(future::ready(None)))
+            .scan(EventParser::default(), |parser, chunk| {
+                let events = match chunk {
+                    Some(Ok(bytes)) => parser.push(&bytes),
+                    Some(Err(err)) => vec![Err(Error::from(err))],
+                    None => parser.finish(),
+                };
+                future::ready(Some(stream::iter(events)))
+            })
+            .flatten())
     }
 
-    /// Create a prediction from an official model
+    /// Create a prediction from a model referenced as **owner/name** or **owner/name:version**.
+    ///
+    /// Official models run their latest version when no version is given.
     pub async fn create_model_prediction(
         &self,
+        model: &str,
         payload: CreateModelPrediction,
     ) -> Result<Prediction, Error> {
-        let path = format!("models/{}/{}/predictions", payload.owner, payload.name);
         let response = self
-            .request(Method::POST, path.as_str())?
-            .json(&serde_json::json!({ "input": payload.input }))
+            .model_prediction_request(model, payload)
+            .await?
+            .send()
+            .await?;
+        self.handle_response::<Prediction>(response).await
+    }
+
+    /// Create a prediction from a model and wait for it to complete.
+    pub async fn run_model(
+        &self,
+        model: &str,
+        payload: CreateModelPrediction,
+        options: &WaitOptions,
+    ) -> Result<Prediction, Error> {
+        let request = self.model_prediction_request(model, payload).await?;
+        self.run_request(request, options).await
+    }
+
+    /// Create a streaming prediction from a model and consume its events.
+    pub async fn stream_model(
+        &self,
+        model: &str,
+        payload: CreateModelPrediction,
+    ) -> Result<impl Stream<Item = Result<PredictionEvent, Error>>, Error> {
+        let request = self
+            .model_prediction_request(model, payload.with_stream(true))
+            .await?;
+        self.stream_request(request).await
+    }
+
+    /// Create a prediction from a deployment referenced as **owner/name**.
+    pub async fn create_deployment_prediction(
+        &self,
+        deployment: &str,
+        payload: CreateModelPrediction,
+    ) -> Result<Prediction, Error> {
+        let response = self
+            .deployment_prediction_request(deployment, payload)
+            .await?
             .send()
             .await?;
         self.handle_response::<Prediction>(response).await
     }
 
+    /// Create a prediction from a deployment and wait for it to complete.
+    pub async fn run_deployment(
+        &self,
+        deployment: &str,
+        payload: CreateModelPrediction,
+        options: &WaitOptions,
+    ) -> Result<Prediction, Error> {
+        let request = self
+            .deployment_prediction_request(deployment, payload)
+            .await?;
+        self.run_request(request, options).await
+    }
+
+    /// Create a streaming prediction from a deployment and consume its events.
+    pub async fn stream_deployment(
+        &self,
+        deployment: &str,
+        payload: CreateModelPrediction,
+    ) -> Result<impl Stream<Item = Result<PredictionEvent, Error>>, Error> {
+        let request = self
+            .deployment_prediction_request(deployment, payload.with_stream(true))
+            .await?;
+        self.stream_request(request).await
+    }
+
+    async fn prediction_request(
+        &self,
+        mut payload: CreatePrediction,
+    ) -> Result<RequestBuilder, Error> {
+        let files = std::mem::take(&mut payload.files);
+        self.resolve_files(&mut payload.input, files, payload.file_encoding)
+            .await?;
+        Ok(self.request(Method::POST, "predictions")?.json(&payload))
+    }
+
+    async fn model_prediction_request(
+        &self,
+        model: &str,
+        mut payload: CreateModelPrediction,
+    ) -> Result<RequestBuilder, Error> {
+        let model = model.parse::<ModelRef>()?;
+        if let Some(version) = model.version {
+            let payload = CreatePrediction {
+                version,
+                input: payload.input,
+                stream: payload.stream,
+                webhook: payload.webhook,
+                webhook_event_filters: payload.webhook_event_filters,
+                files: payload.files,
+                file_encoding: payload.file_encoding,
+            };
+            return self.prediction_request(payload).await;
+        }
+        let files = std::mem::take(&mut payload.files);
+        self.resolve_files(&mut payload.input, files, payload.file_encoding)
+            .await?;
+        let path = format!("models/{}/{}/predictions", model.owner, model.name);
+        Ok(self.request(Method::POST, path.as_str())?.json(&payload))
+    }
+
+    async fn deployment_prediction_request(
+        &self,
+        deployment: &str,
+        mut payload: CreateModelPrediction,
+    ) -> Result<RequestBuilder, Error> {
+        let deployment = deployment.parse::<ModelRef>()?;
+        if deployment.version.is_some() {
+            return Err(Error::InvalidModelRef(deployment.to_string()));
+        }
+        let files = std::mem::take(&mut payload.files);
+        self.resolve_files(&mut payload.input, files, payload.file_encoding)
+            .await?;
+        let path = format!(
+            "deployments/{}/{}/predictions",
+            deployment.owner, deployment.name
+        );
+        Ok(self.request(Method::POST, path.as_str())?.json(&payload))
+    }
+
+    /// Send a prediction creation request then wait for the prediction to complete.
+    async fn run_request(
+        &self,
+        request: RequestBuilder,
+        options: &WaitOptions,
+    ) -> Result<Prediction, Error> {
+        let response = with_prefer_wait(request, options).send().await?;
+        let prediction = self.handle_response::<Prediction>(response).await?;
+        self.wait_for_prediction(prediction, options).await
+    }
+
+    /// Send a streaming prediction creation request then consume the prediction events.
+    async fn stream_request(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<impl Stream<Item = Result<PredictionEvent, Error>>, Error> {
+        let response = request.send().await?;
+        let prediction = self.handle_response::<Prediction>(response).await?;
+        self.stream_prediction(&prediction).await
+    }
+
     /// Cancel a prediction.
     pub async fn cancel_prediction(&self, prediction_id: String) -> Result<(), Error> {
         let path = format!("predictions/{}/cancel", prediction_id);
@@ -172,6 +553,55 @@ impl Client {
         self.handle_response::<ListTrainings>(response).await
     }
 
+    /// Stream trainings, fetching the next pages as needed.
+    pub fn trainings_stream(&self) -> impl Stream<Item = Result<Training, Error>> + '_ {
+        self.paginate::<ListTrainings>("trainings")
+    }
+
+    /// Start a training of a model version.
+    ///
+    /// The new version is pushed to the destination model of the payload, which must already exist.
+    pub async fn create_training(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+        version_id: impl Into<String>,
+        payload: CreateTraining,
+    ) -> Result<Training, Error> {
+        let path = format!(
+            "models/{}/{}/versions/{}/trainings",
+            owner.into(),
+            name.into(),
+            version_id.into()
+        );
+        let response = self
+            .request(Method::POST, path.as_str())?
+            .json(&payload)
+            .send()
+            .await?;
+        self.handle_response::<Training>(response).await
+    }
+
+    /// Poll a training until it reaches a terminal status.
+    ///
+    /// Once succeeded, the **output** of the training holds the version it created.
+    /// Returns an error if the training fails, is canceled or doesn't complete before the timeout.
+    pub async fn wait_for_training(
+        &self,
+        training: Training,
+        options: &WaitOptions,
+    ) -> Result<Training, Error> {
+        let id = training.id.clone();
+        let training = options
+            .poll(
+                training,
+                || self.training(id.clone()),
+                Training::is_terminal,
+            )
+            .await?;
+        training.into_result()
+    }
+
     /// Cancel a training.
     pub async fn cancel_training(&self, training_id: String) -> Result<(), Error> {
         let path = format!("trainings/{}/cancel", training_id);
@@ -191,6 +621,21 @@ impl Client {
         self.handle_response::<ListPublicModels>(response).await
     }
 
+    /// Stream public models, fetching the next pages as needed.
+    pub fn public_models_stream(&self) -> impl Stream<Item = Result<Model, Error>> + '_ {
+        self.paginate::<ListPublicModels>("models")
+    }
+
+    /// Create a model.
+    pub async fn create_model(&self, payload: CreateModel) -> Result<Model, Error> {
+        let response = self
+            .request(Method::POST, "models")?
+            .json(&payload)
+            .send()
+            .await?;
+        self.handle_response::<Model>(response).await
+    }
+
     /// Get model.
     pub async fn model(
         &self,
@@ -213,6 +658,16 @@ impl Client {
         self.handle_response::<ListModelVersions>(response).await
     }
 
+    /// Stream model versions, fetching the next pages as needed.
+    pub fn model_versions_stream(
+        &self,
+        owner: impl Into<String>,
+        name: impl Into<String>,
+    ) -> impl Stream<Item = Result<ModelVersion, Error>> + '_ {
+        let path = format!("models/{}/{}/versions", owner.into(), name.into());
+        self.paginate::<ListModelVersions>(&path)
+    }
+
     /// Get model version.
     pub async fn model_version(
         &self,
@@ -239,7 +694,119 @@ impl Client {
         self.handle_response::<WebHookSecret>(response).await
     }
 
-    fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
+    /// Upload a file with the files API.
+    pub async fn upload_file(&self, file: FileInput) -> Result<File, Error> {
+        let content = file.read().await?;
+        self.upload_content(content).await
+    }
+
+    /// List uploaded files.
+    pub async fn files(&self) -> Result<ListFiles, Error> {
+        let response = self.request(Method::GET, "files")?.send().await?;
+        self.handle_response::<ListFiles>(response).await
+    }
+
+    /// Stream uploaded files, fetching the next pages as needed.
+    pub fn files_stream(&self) -> impl Stream<Item = Result<File, Error>> + '_ {
+        self.paginate::<ListFiles>("files")
+    }
+
+    /// Get an uploaded file.
+    pub async fn file(&self, file_id: impl Into<String>) -> Result<File, Error> {
+        let path = format!("files/{}", file_id.into());
+        let response = self.request(Method::GET, path.as_str())?.send().await?;
+        self.handle_response::<File>(response).await
+    }
+
+    /// Delete an uploaded file.
+    pub async fn delete_file(&self, file_id: impl Into<String>) -> Result<(), Error> {
+        let path = format!("files/{}", file_id.into());
+        let response = self.request(Method::DELETE, path.as_str())?.send().await?;
+        self.handle_empty_response(response).await
+    }
+
+    /// Turn a file into a value accepted as model input, either a data URL or the URL of an uploaded file.
+    pub async fn encode_file(
+        &self,
+        file: FileInput,
+        encoding: FileEncoding,
+    ) -> Result<String, Error> {
+        if let FileInput::Url(url) = file {
+            return Ok(url);
+        }
+        let content = file.read().await?;
+        match encoding {
+            FileEncoding::DataUrl => Ok(content.to_data_url()),
+            FileEncoding::Auto if content.data.len() <= MAX_DATA_URL_SIZE => {
+                Ok(content.to_data_url())
+            }
+            _ => Ok(self.upload_content(content).await?.urls.get),
+        }
+    }
+
+    /// Download a file, such as a prediction output, into memory.
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, Error> {
+        let response = self.download_request(url)?.send().await?;
+        if !response.status().is_success() {
+            return Err(self.handle_error(response).await);
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Download a file, such as a prediction output, to `path`.
+    pub async fn download_to(&self, url: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+        let response = self.download_request(url)?.send().await?;
+        if !response.status().is_success() {
+            return Err(self.handle_error(response).await);
+        }
+        let mut file = tokio::fs::File::create(path).await?;
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks.next().await {
+            file.write_all(&chunk?).await?;
+        }
+        file.flush().await?;
+        Ok(())
+    }
+
+    async fn upload_content(&self, content: FileContent) -> Result<File, Error> {
+        let part = Part::bytes(content.data)
+            .file_name(content.filename)
+            .mime_str(&content.content_type)?;
+        let form = Form::new().part("content", part);
+        let response = self
+            .request(Method::POST, "files")?
+            .multipart(form)
+            .send()
+            .await?;
+        self.handle_response::<File>(response).await
+    }
+
+    async fn resolve_files(
+        &self,
+        input: &mut serde_json::Value,
+        files: Vec<(String, FileInput)>,
+        encoding: FileEncoding,
+    ) -> Result<(), Error> {
+        let mut values = Vec::with_capacity(files.len());
+        for (name, file) in files {
+            values.push((name, self.encode_file(file, encoding).await?));
+        }
+        files::insert_inputs(input, values)
+    }
+
+    /// Build a GET request to a URL returned by the API, e.g. a file or a stream.
+    ///
+    /// Only URLs of the API origin get the API key, other origins such as **stream.replicate.com** are fetched without credentials.
+    fn download_request(&self, url: &str) -> Result<RequestBuilder, Error> {
+        let url = Url::parse(url).map_err(|err| Error::UrlParse(err.to_string()))?;
+        if url.origin() == self.base_url.origin() {
+            Ok(self.http_client.get(url))
+        } else {
+            Ok(self.download_client.get(url))
+        }
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
         let url = self
             .base_url
             .join(path)
@@ -247,7 +814,18 @@ impl Client {
         Ok(self.http_client.request(method, url))
     }
 
-    async fn handle_response<T>(&self, response: Response) -> Result<T, Error>
+    /// Fetch `path` then follow the **next** URL of each page.
+    fn paginate<P: Page + 'static>(
+        &self,
+        path: &str,
+    ) -> impl Stream<Item = Result<P::Item, Error>> + '_ {
+        pagination::paginate(path.to_string(), move |url| async move {
+            let response = self.request(Method::GET, &url)?.send().await?;
+            self.handle_response::<P>(response).await
+        })
+    }
+
+    pub(crate) async fn handle_response<T>(&self, response: Response) -> Result<T, Error>
     where
         T: serde::de::DeserializeOwned,
     {
@@ -259,37 +837,63 @@ impl Client {
                 Err(err) => Err(Error::HttpRequest(err)),
             }
         } else {
-            match status {
-                StatusCode::BAD_REQUEST => {
-                    let error_msg = response.text().await?;
-                    Err(Error::BadRequest(error_msg))
-                }
-                StatusCode::UNAUTHORIZED => {
-                    let error_msg = response.text().await?;
-                    Err(Error::Unauthorized(error_msg))
-                }
-                StatusCode::FORBIDDEN => {
-                    let error_msg = response.text().await?;
-                    Err(Error::Forbidden(error_msg))
-                }
-                StatusCode::TOO_MANY_REQUESTS => {
-                    let error_msg = response.text().await?;
-                    Err(Error::RateLimited(error_msg))
-                }
-                StatusCode::INTERNAL_SERVER_ERROR => {
-                    let error_msg = response.text().await?;
-                    Err(Error::InternalServerError(error_msg))
-                }
-                StatusCode::SERVICE_UNAVAILABLE => {
-                    let error_msg = response.text().await?;
-                    Err(Error::ServiceUnavailable(error_msg))
-                }
-                status => Err(Error::UnexpectedStatus(status)),
-            }
+            Err(self.handle_error(response).await)
+        }
+    }
+
+    /// Handle a response without body, such as the one of a deletion.
+    pub(crate) async fn handle_empty_response(&self, response: Response) -> Result<(), Error> {
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(self.handle_error(response).await)
+        }
+    }
+
+    /// Map an unsuccessful response to the matching error variant.
+    pub(crate) async fn handle_error(&self, response: Response) -> Error {
+        let status = response.status();
+        let error_msg = match response.text().await {
+            Ok(error_msg) => error_msg,
+            Err(err) => return Error::HttpRequest(err),
+        };
+        match status {
+            StatusCode::BAD_REQUEST => Error::BadRequest(error_msg),
+            StatusCode::UNAUTHORIZED => Error::Unauthorized(error_msg),
+            StatusCode::FORBIDDEN => Error::Forbidden(error_msg),
+            StatusCode::TOO_MANY_REQUESTS => Error::RateLimited(error_msg),
+            StatusCode::INTERNAL_SERVER_ERROR => Error::InternalServerError(error_msg),
+            StatusCode::SERVICE_UNAVAILABLE => Error::ServiceUnavailable(error_msg),
+            status => Error::UnexpectedStatus(status),
         }
     }
 }
 
+/// Whether the API rejected the deletion of a deployment because it isn't idle yet.
+///
+/// Decided on the status and detail of the problem details body rather than on the whole message.
+fn is_deployment_in_use(err: &Error) -> bool {
+    match err {
+        Error::UnexpectedStatus(StatusCode::CONFLICT) => true,
+        Error::BadRequest(body) => ApiError::from_body(body).is_some_and(|error| {
+            error.status == StatusCode::CONFLICT.as_u16()
+                || (error.status == StatusCode::BAD_REQUEST.as_u16()
+                    && DEPLOYMENT_IN_USE_DETAILS
+                        .iter()
+                        .any(|detail| error.detail.contains(detail)))
+        }),
+        _ => false,
+    }
+}
+
+/// Ask the API to hold the request open until the prediction completes or the wait time elapses.
+fn with_prefer_wait(request: RequestBuilder, options: &WaitOptions) -> RequestBuilder {
+    match options.prefer_wait {
+        Some(seconds) => request.header(PREFER_HEADER, format!("wait={}", seconds)),
+        None => request,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
     #[serde(rename = "type")]
@@ -347,6 +951,88 @@ pub struct Model {
     pub license_url: Option<String>,
     pub run_count: u64,
     pub cover_image_url: Option<String>,
+    pub latest_version: Option<ModelVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateModel {
+    /// The name of the user or organization that will own the model.
+    pub owner: String,
+
+    /// The name of the model.
+    pub name: String,
+
+    /// Whether the model is public or private.
+    pub visibility: ModelVisibility,
+
+    /// The SKU for the hardware used to run the model e.g. **gpu-a40-large**.
+    pub hardware: String,
+
+    /// A description of the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// A URL for the model's source code on GitHub.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github_url: Option<String>,
+
+    /// A URL for the model's paper.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paper_url: Option<String>,
+
+    /// A URL for the model's license.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_url: Option<String>,
+
+    /// A URL for the model's cover image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cover_image_url: Option<String>,
+}
+
+impl CreateModel {
+    pub fn new(
+        owner: impl Into<String>,
+        name: impl Into<String>,
+        visibility: ModelVisibility,
+        hardware: impl Into<String>,
+    ) -> Self {
+        Self {
+            owner: owner.into(),
+            name: name.into(),
+            visibility,
+            hardware: hardware.into(),
+            description: None,
+            github_url: None,
+            paper_url: None,
+            license_url: None,
+            cover_image_url: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_github_url(mut self, github_url: impl Into<String>) -> Self {
+        self.github_url = Some(github_url.into());
+        self
+    }
+
+    pub fn with_paper_url(mut self, paper_url: impl Into<String>) -> Self {
+        self.paper_url = Some(paper_url.into());
+        self
+    }
+
+    pub fn with_license_url(mut self, license_url: impl Into<String>) -> Self {
+        self.license_url = Some(license_url.into());
+        self
+    }
+
+    pub fn with_cover_image_url(mut self, cover_image_url: impl Into<String>) -> Self {
+        self.cover_image_url = Some(cover_image_url.into());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -377,25 +1063,50 @@ pub struct CreateDeployment {
     pub max_instances: u16,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Changes to apply to a deployment. Fields left unset are not changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateDeployment {
-    /// The name of the deployment.
-    pub name: String,
-
-    /// The full name of the model that you want to deploy e.g. stability-ai/sdxl.
-    pub model: String,
-
     /// The 64-character string ID of the model version that you want to deploy.
-    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 
     /// The SKU for the hardware used to run the model.
-    pub hardware: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hardware: Option<String>,
+
+    /// The minimum number of instances for scaling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_instances: Option<u16>,
 
     /// The maximum number of instances for scaling.
-    pub min_instances: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_instances: Option<u16>,
+}
 
-    /// The minimum number of instances for scaling.
-    pub max_instances: u16,
+impl UpdateDeployment {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn with_hardware(mut self, hardware: impl Into<String>) -> Self {
+        self.hardware = Some(hardware.into());
+        self
+    }
+
+    pub fn with_min_instances(mut self, min_instances: u16) -> Self {
+        self.min_instances = Some(min_instances);
+        self
+    }
+
+    pub fn with_max_instances(mut self, max_instances: u16) -> Self {
+        self.max_instances = Some(max_instances);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -417,11 +1128,19 @@ pub struct DeploymentRelease {
     pub number: u64,
     pub model: String,
     pub version: String,
-    pub created_at: String,
+    pub created_at: Timestamp,
     pub created_by: Account,
     pub configuration: DeploymentConfiguration,
 }
 
+impl DeploymentRelease {
+    /// Creation time of the release, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        crate::timestamp::parse(&self.created_at)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentConfiguration {
     pub hardware: String,
@@ -437,6 +1156,12 @@ pub struct CreatePrediction {
     /// The model's input as a JSON object.
     pub input: serde_json::Value,
 
+    /// Request a URL to receive streaming output using server-sent events.
+    ///
+    /// Only supported by language models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
     /// An HTTPS URL for receiving a webhook when the prediction has new output.
     ///
     /// The webhook will be a POST request where the request body is the same as the response body of the get prediction operation.
@@ -444,6 +1169,7 @@ pub struct CreatePrediction {
     /// **Notes**:
     ///     - Retries a few times in case of network problems.
     ///     - It doesn't follow redirects.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub webhook: Option<String>,
 
     /// Events triggering webhook requests.
@@ -466,19 +1192,152 @@ pub struct CreatePrediction {
     ///     "webhook_events_filter": ["start", "completed"]
     /// }
     /// ```
+    #[serde(
+        rename = "webhook_events_filter",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub webhook_event_filters: Option<Vec<WebHookEvent>>,
+
+    /// Files set as input fields when the prediction is created.
+    #[serde(skip)]
+    pub files: Vec<(String, FileInput)>,
+
+    /// How the files are sent.
+    #[serde(skip)]
+    pub file_encoding: FileEncoding,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CreateModelPrediction {
-    /// Model owner
-    pub owner: String,
+impl CreatePrediction {
+    pub fn new(version: impl Into<String>, input: serde_json::Value) -> Self {
+        Self {
+            version: version.into(),
+            input,
+            stream: None,
+            webhook: None,
+            webhook_event_filters: None,
+            files: Vec::new(),
+            file_encoding: FileEncoding::default(),
+        }
+    }
 
-    /// Model name
-    pub name: String,
+    pub fn with_stream(mut self, stream: bool) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    pub fn with_webhook(mut self, webhook: impl Into<String>) -> Self {
+        self.webhook = Some(webhook.into());
+        self
+    }
+
+    pub fn with_webhook_event_filters(mut self, events: Vec<WebHookEvent>) -> Self {
+        self.webhook_event_filters = Some(events);
+        self
+    }
+
+    /// Set the `name` input field to `file`, converted when the prediction is created.
+    pub fn with_file(mut self, name: impl Into<String>, file: FileInput) -> Self {
+        self.files.push((name.into(), file));
+        self
+    }
+
+    pub fn with_file_encoding(mut self, encoding: FileEncoding) -> Self {
+        self.file_encoding = encoding;
+        self
+    }
+
+    /// Check the input, file inputs included, against the input schema of `version` before sending it.
+    pub fn validate(&self, version: &ModelVersion) -> Result<(), Error> {
+        let mut input = self.input.clone();
+        let files = self
+            .files
+            .iter()
+            .map(|(name, _)| (name.clone(), String::new()))
+            .collect();
+        files::insert_inputs(&mut input, files)?;
+        version.validate_input(&input)
+    }
+}
 
+/// Prediction created from a model or deployment, which is referenced in the URL rather than in the body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateModelPrediction {
     /// The model's input as a JSON object.
     pub input: serde_json::Value,
+
+    /// Request a URL to receive streaming output using server-sent events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
+    /// An HTTPS URL for receiving a webhook when the prediction has new output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    /// Events triggering webhook requests.
+    #[serde(
+        rename = "webhook_events_filter",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub webhook_event_filters: Option<Vec<WebHookEvent>>,
+
+    /// Files set as input fields when the prediction is created.
+    #[serde(skip)]
+    pub files: Vec<(String, FileInput)>,
+
+    /// How the files are sent.
+    #[serde(skip)]
+    pub file_encoding: FileEncoding,
+}
+
+impl CreateModelPrediction {
+    pub fn new(input: serde_json::Value) -> Self {
+        Self {
+            input,
+            stream: None,
+            webhook: None,
+            webhook_event_filters: None,
+            files: Vec::new(),
+            file_encoding: FileEncoding::default(),
+        }
+    }
+
+    pub fn with_stream(mut self, stream: bool) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    pub fn with_webhook(mut self, webhook: impl Into<String>) -> Self {
+        self.webhook = Some(webhook.into());
+        self
+    }
+
+    pub fn with_webhook_event_filters(mut self, events: Vec<WebHookEvent>) -> Self {
+        self.webhook_event_filters = Some(events);
+        self
+    }
+
+    /// Set the `name` input field to `file`, converted when the prediction is created.
+    pub fn with_file(mut self, name: impl Into<String>, file: FileInput) -> Self {
+        self.files.push((name.into(), file));
+        self
+    }
+
+    pub fn with_file_encoding(mut self, encoding: FileEncoding) -> Self {
+        self.file_encoding = encoding;
+        self
+    }
+
+    /// Check the input, file inputs included, against the input schema of `version` before sending it.
+    pub fn validate(&self, version: &ModelVersion) -> Result<(), Error> {
+        let mut input = self.input.clone();
+        let files = self
+            .files
+            .iter()
+            .map(|(name, _)| (name.clone(), String::new()))
+            .collect();
+        files::insert_inputs(&mut input, files)?;
+        version.validate_input(&input)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -509,13 +1368,83 @@ pub struct Prediction {
     pub status: PredictionStatus,
     pub urls: PredictionUrls,
     pub logs: Option<String>,
+    pub error: Option<serde_json::Value>,
     pub data_removed: Option<bool>,
-    pub created_at: String,
-    pub started_at: Option<String>,
-    pub completed_at: Option<String>,
+    pub created_at: Timestamp,
+    pub started_at: Option<Timestamp>,
+    pub completed_at: Option<Timestamp>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Prediction {
+    /// Time spent by the model to run the prediction, as reported by the API.
+    pub fn predict_time(&self) -> Option<Duration> {
+        let seconds = self.metrics.as_ref()?.predict_time?;
+        Duration::try_from_secs_f64(seconds).ok()
+    }
+
+    /// Creation time of the prediction, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        crate::timestamp::parse(&self.created_at)
+    }
+
+    /// Start time of the prediction, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn started_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.started_at.as_deref().and_then(crate::timestamp::parse)
+    }
+
+    /// Completion time of the prediction, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn completed_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.completed_at
+            .as_deref()
+            .and_then(crate::timestamp::parse)
+    }
+
+    /// Time spent waiting for the prediction to start, including cold boots.
+    #[cfg(feature = "chrono")]
+    pub fn queue_time(&self) -> Option<Duration> {
+        crate::timestamp::elapsed(Some(&self.created_at), self.started_at.as_deref())
+    }
+
+    /// Time between the start and the completion of the prediction.
+    #[cfg(feature = "chrono")]
+    pub fn run_time(&self) -> Option<Duration> {
+        crate::timestamp::elapsed(self.started_at.as_deref(), self.completed_at.as_deref())
+    }
+
+    /// URLs of the files generated by the prediction, to use with [`Client::download`].
+    pub fn output_urls(&self) -> Vec<&str> {
+        let mut urls = Vec::new();
+        if let Some(output) = &self.output {
+            files::collect_urls(output, &mut urls);
+        }
+        urls
+    }
+
+    /// Turn a failed or canceled prediction into an error, including its logs.
+    pub fn into_result(self) -> Result<Self, Error> {
+        match self.status {
+            PredictionStatus::Failed => Err(Error::PredictionFailed {
+                error: match self.error {
+                    Some(serde_json::Value::String(error)) => error,
+                    Some(error) => error.to_string(),
+                    None => "unknown error".to_string(),
+                },
+                id: self.id,
+                logs: self.logs,
+            }),
+            PredictionStatus::Canceled => Err(Error::PredictionCanceled {
+                id: self.id,
+                logs: self.logs,
+            }),
+            _ => Ok(self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum PredictionStatus {
     Starting,
@@ -525,15 +1454,25 @@ pub enum PredictionStatus {
     Canceled,
 }
 
+impl PredictionStatus {
+    /// Whether the prediction has stopped running.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Canceled)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionMetrics {
-    pub predict_time: f64,
+    /// Seconds spent by the model to run the prediction. Only set once the prediction has completed.
+    pub predict_time: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionUrls {
     pub get: String,
     pub cancel: String,
+    /// Server-sent events URL. Only set for predictions created with **stream = true**.
+    pub stream: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -552,23 +1491,149 @@ pub struct ListTrainings {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Training {
-    pub completed_at: String,
-    pub created_at: String,
+    pub completed_at: Option<Timestamp>,
+    pub created_at: Timestamp,
     pub id: String,
     pub input: serde_json::Value,
-    pub metrics: TrainingMetrics,
-    pub output: TrainingOutput,
-    pub started_at: String,
-    pub source: Source,
-    pub status: String,
+    pub metrics: Option<TrainingMetrics>,
+    pub output: Option<TrainingOutput>,
+    pub started_at: Option<Timestamp>,
+    pub source: Option<Source>,
+    pub status: TrainingStatus,
     pub urls: TrainingUrls,
     pub model: String,
     pub version: String,
+    pub logs: Option<String>,
+    pub error: Option<serde_json::Value>,
+}
+
+impl Training {
+    /// Whether the training has stopped running.
+    pub fn is_terminal(&self) -> bool {
+        self.status.is_terminal()
+    }
+
+    /// Time spent by the model to train, as reported by the API.
+    pub fn predict_time(&self) -> Option<Duration> {
+        let seconds = self.metrics.as_ref()?.predict_time?;
+        Duration::try_from_secs_f64(seconds).ok()
+    }
+
+    /// Creation time of the training, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        crate::timestamp::parse(&self.created_at)
+    }
+
+    /// Start time of the training, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn started_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.started_at.as_deref().and_then(crate::timestamp::parse)
+    }
+
+    /// Completion time of the training, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn completed_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.completed_at
+            .as_deref()
+            .and_then(crate::timestamp::parse)
+    }
+
+    /// Time spent waiting for the training to start.
+    #[cfg(feature = "chrono")]
+    pub fn queue_time(&self) -> Option<Duration> {
+        crate::timestamp::elapsed(Some(&self.created_at), self.started_at.as_deref())
+    }
+
+    /// Time between the start and the completion of the training.
+    #[cfg(feature = "chrono")]
+    pub fn run_time(&self) -> Option<Duration> {
+        crate::timestamp::elapsed(self.started_at.as_deref(), self.completed_at.as_deref())
+    }
+
+    /// Turn a failed or canceled training into an error, including its logs.
+    pub fn into_result(self) -> Result<Self, Error> {
+        match self.status {
+            TrainingStatus::Failed => Err(Error::TrainingFailed {
+                error: match self.error {
+                    Some(serde_json::Value::String(error)) => error,
+                    Some(error) => error.to_string(),
+                    None => "unknown error".to_string(),
+                },
+                id: self.id,
+                logs: self.logs,
+            }),
+            TrainingStatus::Canceled => Err(Error::TrainingCanceled {
+                id: self.id,
+                logs: self.logs,
+            }),
+            _ => Ok(self),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTraining {
+    /// The model to push the trained version to e.g. **owner/name**.
+    pub destination: String,
+
+    /// The training input as a JSON object.
+    pub input: serde_json::Value,
+
+    /// An HTTPS URL for receiving a webhook when the training has new output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    /// Events triggering webhook requests.
+    #[serde(
+        rename = "webhook_events_filter",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub webhook_event_filters: Option<Vec<WebHookEvent>>,
+}
+
+impl CreateTraining {
+    pub fn new(destination: impl Into<String>, input: serde_json::Value) -> Self {
+        Self {
+            destination: destination.into(),
+            input,
+            webhook: None,
+            webhook_event_filters: None,
+        }
+    }
+
+    pub fn with_webhook(mut self, webhook: impl Into<String>) -> Self {
+        self.webhook = Some(webhook.into());
+        self
+    }
+
+    pub fn with_webhook_event_filters(mut self, events: Vec<WebHookEvent>) -> Self {
+        self.webhook_event_filters = Some(events);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrainingMetrics {
-    pub predict_time: f64,
+    /// Seconds spent by the model to train. Only set once the training has completed.
+    pub predict_time: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrainingStatus {
+    Starting,
+    Processing,
+    Succeeded,
+    Failed,
+    Canceled,
+}
+
+impl TrainingStatus {
+    /// Whether the training has stopped running.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Canceled)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -599,11 +1664,304 @@ pub struct ListModelVersions {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelVersion {
     pub id: String,
-    pub created_at: String,
+    pub created_at: Timestamp,
     pub cog_version: String,
+
+    /// OpenAPI definition of the model's input and output.
+    pub openapi_schema: Option<serde_json::Value>,
+}
+
+impl ModelVersion {
+    /// Creation time of the version, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        crate::timestamp::parse(&self.created_at)
+    }
+
+    /// Schema of the model's input, if the version has an OpenAPI definition.
+    pub fn input_schema(&self) -> Option<InputSchema> {
+        self.openapi_schema
+            .as_ref()
+            .and_then(InputSchema::from_openapi)
+    }
+
+    /// JSON schema of the model's output, if the version has an OpenAPI definition.
+    pub fn output_schema(&self) -> Option<&serde_json::Value> {
+        self.openapi_schema
+            .as_ref()?
+            .pointer("/components/schemas/Output")
+    }
+
+    /// Check `input` against the input schema of the version.
+    ///
+    /// Succeeds when the version has no schema.
+    pub fn validate_input(&self, input: &serde_json::Value) -> Result<(), Error> {
+        match self.input_schema() {
+            Some(schema) => schema.validate(input),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebHookSecret {
     pub key: String,
 }
+
+impl_page!(
+    ListCollections => Collection,
+    ListPublicModels => Model,
+    ListDeployments => Deployment,
+    ListPredictions => Prediction,
+    ListTrainings => Training,
+    ListModelVersions => ModelVersion,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn prediction(status: &str) -> Prediction {
+        serde_json::from_value(serde_json::json!({
+            "id": "gm3qorzdhgbfurvjtvhg6dckhu",
+            "model": "replicate/hello-world",
+            "version": "5c7d5dc6dd8bf75c1acaa8565735e7986bc5b66206b55cca93cb72c9bf15ccaa",
+            "input": { "text": "Alice" },
+            "output": null,
+            "logs": "Traceback (most recent call last): ...",
+            "error": "CUDA out of memory",
+            "status": status,
+            "created_at": "2023-09-08T16:19:34.765994657Z",
+            "urls": {
+                "get": "https://api.replicate.com/v1/predictions/gm3qorzdhgbfurvjtvhg6dckhu",
+                "cancel": "https://api.replicate.com/v1/predictions/gm3qorzdhgbfurvjtvhg6dckhu/cancel"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn should_only_retry_deletion_of_deployments_in_use() {
+        let in_use = Error::BadRequest(
+            r#"{"title":"Deployment cannot be deleted","detail":"Deployments can only be deleted if they have been offline and unused for at least 15 minutes.","status":400}"#.to_string(),
+        );
+        assert!(is_deployment_in_use(&in_use));
+        assert!(is_deployment_in_use(&Error::UnexpectedStatus(
+            StatusCode::CONFLICT
+        )));
+
+        let invalid = Error::BadRequest(
+            r#"{"title":"Invalid input","detail":"Deployment name must be unused and lowercase.","status":400}"#.to_string(),
+        );
+        assert!(!is_deployment_in_use(&invalid));
+
+        let free_text = Error::BadRequest(
+            "Deployments can only be deleted if they have been offline and unused".to_string(),
+        );
+        assert!(!is_deployment_in_use(&free_text));
+        assert!(!is_deployment_in_use(&Error::NotFound(String::new())));
+    }
+
+    #[test]
+    fn should_detect_terminal_statuses() {
+        assert!(!PredictionStatus::Starting.is_terminal());
+        assert!(!PredictionStatus::Processing.is_terminal());
+        assert!(PredictionStatus::Succeeded.is_terminal());
+        assert!(PredictionStatus::Failed.is_terminal());
+        assert!(PredictionStatus::Canceled.is_terminal());
+    }
+
+    #[test]
+    fn should_return_error_for_failed_prediction() {
+        match prediction("failed").into_result() {
+            Err(Error::PredictionFailed { id, error, logs }) => {
+                assert_eq!(id, "gm3qorzdhgbfurvjtvhg6dckhu");
+                assert_eq!(error, "CUDA out of memory");
+                assert!(logs.unwrap().starts_with("Traceback"));
+            }
+            other => panic!("Expected 'PredictionFailed' error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_return_error_for_canceled_prediction() {
+        assert!(matches!(
+            prediction("canceled").into_result(),
+            Err(Error::PredictionCanceled { .. })
+        ));
+        assert!(prediction("succeeded").into_result().is_ok());
+    }
+
+    #[test]
+    fn should_compute_prediction_durations() {
+        let mut prediction: Prediction = serde_json::from_value(serde_json::json!({
+            "id": "gm3qorzdhgbfurvjtvhg6dckhu",
+            "model": "replicate/hello-world",
+            "version": "5c7d5dc6dd8bf75c1acaa8565735e7986bc5b66206b55cca93cb72c9bf15ccaa",
+            "status": "processing",
+            "metrics": {},
+            "created_at": "2023-09-08T16:19:34.765994657Z",
+            "started_at": "2023-09-08T16:19:36.265994657Z",
+            "urls": {
+                "get": "https://api.replicate.com/v1/predictions/gm3qorzdhgbfurvjtvhg6dckhu",
+                "cancel": "https://api.replicate.com/v1/predictions/gm3qorzdhgbfurvjtvhg6dckhu/cancel"
+            }
+        }))
+        .unwrap();
+        assert_eq!(prediction.predict_time(), None);
+
+        prediction.metrics = Some(PredictionMetrics {
+            predict_time: Some(2.5),
+        });
+        assert_eq!(
+            prediction.predict_time(),
+            Some(std::time::Duration::from_millis(2500))
+        );
+
+        #[cfg(feature = "chrono")]
+        {
+            assert_eq!(
+                prediction.queue_time(),
+                Some(std::time::Duration::from_millis(1500))
+            );
+            assert_eq!(prediction.run_time(), None);
+            assert!(prediction.started_at_datetime().is_some());
+            assert_eq!(prediction.completed_at_datetime(), None);
+        }
+    }
+
+    #[test]
+    fn should_deserialize_running_training() {
+        let training: Training = serde_json::from_value(serde_json::json!({
+            "id": "zz4ibbonubfz7carwiefibzgga",
+            "model": "stability-ai/sdxl",
+            "version": "39ed52f2a78e934b3ba6e2a89f5b1c712de7dfea535525255b1aa35c5565e08b",
+            "input": { "input_images": "https://example.com/images.zip" },
+            "status": "processing",
+            "created_at": "2023-09-08T16:32:56.990893084Z",
+            "urls": {
+                "get": "https://api.replicate.com/v1/trainings/zz4ibbonubfz7carwiefibzgga",
+                "cancel": "https://api.replicate.com/v1/trainings/zz4ibbonubfz7carwiefibzgga/cancel"
+            }
+        }))
+        .unwrap();
+
+        assert!(!training.is_terminal());
+        assert!(training.output.is_none());
+        assert!(matches!(
+            Training {
+                status: TrainingStatus::Failed,
+                ..training
+            }
+            .into_result(),
+            Err(Error::TrainingFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn should_serialize_model_prediction_without_reference() {
+        let payload = CreateModelPrediction::new(serde_json::json!({ "prompt": "A baby dragon" }))
+            .with_stream(true)
+            .with_file("image", FileInput::url("https://example.com/dragon.png"));
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({
+                "input": { "prompt": "A baby dragon" },
+                "stream": true
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_send_api_key_to_stream_origin() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = socket.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8(request).unwrap().to_lowercase()
+        });
+
+        let config = Config::new("r8_test").with_base_url("http://127.0.0.1:9/v1/");
+        let client = Client::new(config).unwrap();
+        let mut prediction = prediction("processing");
+        prediction.urls.stream = Some(format!("http://{}/v1/streams/qoxq-rx4z", address));
+        let stream = client.stream_prediction(&prediction).await.unwrap();
+        assert_eq!(stream.collect::<Vec<_>>().await.len(), 0);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("get /v1/streams/qoxq-rx4z"));
+        assert!(!request.contains("authorization"));
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_input_before_sending() {
+        let config = Config::new("r8_test").with_base_url("http://127.0.0.1:9/");
+        let client = Client::new(config).unwrap();
+        let version: ModelVersion = serde_json::from_value(serde_json::json!({
+            "id": "5c7d5dc6dd8bf75c1acaa8565735e7986bc5b66206b55cca93cb72c9bf15ccaa",
+            "created_at": "2023-09-08T16:19:34.765994657Z",
+            "cog_version": "0.8.6",
+            "openapi_schema": {
+                "components": {
+                    "schemas": {
+                        "Input": {
+                            "type": "object",
+                            "required": ["text"],
+                            "properties": { "text": { "type": "string" } }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let payload = CreatePrediction::new(version.id.clone(), serde_json::json!({ "text": 42 }));
+
+        let result = client.create_prediction_validated(payload, &version).await;
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn should_serialize_partial_deployment_update() {
+        let payload = UpdateDeployment::new().with_max_instances(5);
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({ "max_instances": 5 })
+        );
+    }
+
+    #[test]
+    fn should_serialize_create_model() {
+        let payload = CreateModel::new(
+            "alice",
+            "my-model",
+            ModelVisibility::Private,
+            "gpu-a40-large",
+        )
+        .with_description("A fine-tuned model");
+
+        assert_eq!(
+            serde_json::to_value(payload).unwrap(),
+            serde_json::json!({
+                "owner": "alice",
+                "name": "my-model",
+                "visibility": "private",
+                "hardware": "gpu-a40-large",
+                "description": "A fine-tuned model"
+            })
+        );
+    }
+}
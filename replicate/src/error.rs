@@ -1,5 +1,8 @@
 use reqwest::StatusCode;
 use serde::Deserialize;
+use std::time::Duration;
+
+use crate::{schema::InputError, webhooks::WebhookError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -44,16 +47,63 @@ pub enum Error {
 
     #[error("API error: {0}")]
     Api(ApiError),
+
+    #[error("Prediction {id} failed: {error}")]
+    PredictionFailed {
+        id: String,
+        error: String,
+        logs: Option<String>,
+    },
+
+    #[error("Prediction {id} was canceled")]
+    PredictionCanceled { id: String, logs: Option<String> },
+
+    #[error("Training {id} failed: {error}")]
+    TrainingFailed {
+        id: String,
+        error: String,
+        logs: Option<String>,
+    },
+
+    #[error("Training {id} was canceled")]
+    TrainingCanceled { id: String, logs: Option<String> },
+
+    #[error("Timed out after {0:?}")]
+    Timeout(Duration),
+
+    #[error("Invalid input: {}", format_input_errors(.0))]
+    InvalidInput(Vec<InputError>),
+
+    #[error("Invalid file input: {0}")]
+    InvalidFileInput(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid model reference, expected owner/name[:version]: {0}")]
+    InvalidModelRef(String),
+
+    #[error("Prediction {0} can't be streamed")]
+    StreamUnavailable(String),
+
+    #[error("Webhook error: {0}")]
+    Webhook(#[from] WebhookError),
+}
+
+fn format_input_errors(errors: &[InputError]) -> String {
+    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    errors.join(", ")
 }
 
 #[derive(Debug, Deserialize, PartialEq, Eq, thiserror::Error)]
 #[error("Error response: {status} {kind}")]
 pub struct ApiError {
     /// URI that identifies the error type.
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub kind: String,
 
     /// Short human-readable summary of the error.
+    #[serde(default)]
     pub title: String,
 
     /// HTTP status code.
@@ -63,5 +113,15 @@ pub struct ApiError {
     pub detail: String,
 
     /// URI that identifies the specific occurrence of the error.
+    #[serde(default)]
     pub instance: String,
 }
+
+impl ApiError {
+    /// Parse a problem details body, such as the message of [`Error::BadRequest`].
+    ///
+    /// Returns **None** when the body has no **status** and **detail** fields.
+    pub fn from_body(body: &str) -> Option<Self> {
+        serde_json::from_str(body).ok()
+    }
+}
@@ -0,0 +1,267 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::{error::Error, pagination::impl_page, timestamp::Timestamp};
+
+/// Files up to this size are sent as data URLs by [`FileEncoding::Auto`].
+pub const MAX_DATA_URL_SIZE: usize = 256 * 1024;
+
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// A file uploaded with the files API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct File {
+    pub id: String,
+
+    /// The name of the file.
+    pub name: String,
+
+    /// The content type of the file e.g. **image/png**.
+    pub content_type: String,
+
+    /// The size of the file in bytes.
+    pub size: u64,
+
+    pub etag: Option<String>,
+
+    /// Checksums of the file content by algorithm e.g. **sha256**.
+    #[serde(default)]
+    pub checksums: HashMap<String, String>,
+
+    /// User-provided metadata associated with the file.
+    pub metadata: Option<serde_json::Value>,
+
+    pub created_at: Timestamp,
+
+    /// When the file is deleted, if it has an expiration.
+    pub expires_at: Option<Timestamp>,
+
+    pub urls: FileUrls,
+}
+
+impl File {
+    /// Creation time of the file, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn created_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        crate::timestamp::parse(&self.created_at)
+    }
+
+    /// Expiration time of the file, parsed.
+    #[cfg(feature = "chrono")]
+    pub fn expires_at_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expires_at.as_deref().and_then(crate::timestamp::parse)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileUrls {
+    /// URL of the file, usable as a model input.
+    pub get: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFiles {
+    pub next: Option<String>,
+    pub previous: Option<String>,
+    pub results: Vec<File>,
+}
+
+impl_page!(ListFiles => File);
+
+/// A file passed as a model input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileInput {
+    /// A local file, read when the prediction is created.
+    Path(PathBuf),
+
+    /// In-memory content.
+    Bytes {
+        data: Vec<u8>,
+        filename: String,
+        content_type: Option<String>,
+    },
+
+    /// A file already reachable by URL, passed as is.
+    Url(String),
+}
+
+impl FileInput {
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Self::Path(path.into())
+    }
+
+    pub fn bytes(data: impl Into<Vec<u8>>, filename: impl Into<String>) -> Self {
+        Self::Bytes {
+            data: data.into(),
+            filename: filename.into(),
+            content_type: None,
+        }
+    }
+
+    pub fn url(url: impl Into<String>) -> Self {
+        Self::Url(url.into())
+    }
+
+    /// Read the content of the file along with its name and content type.
+    ///
+    /// The content type is guessed from the file extension when not provided.
+    pub(crate) async fn read(self) -> Result<FileContent, Error> {
+        let (data, filename, content_type) = match self {
+            Self::Path(path) => {
+                let data = tokio::fs::read(&path).await?;
+                let filename = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                (data, filename, None)
+            }
+            Self::Bytes {
+                data,
+                filename,
+                content_type,
+            } => (data, filename, content_type),
+            Self::Url(url) => return Err(Error::InvalidFileInput(url)),
+        };
+        let content_type = content_type.unwrap_or_else(|| {
+            mime_guess::from_path(&filename)
+                .first_raw()
+                .unwrap_or(DEFAULT_CONTENT_TYPE)
+                .to_string()
+        });
+        Ok(FileContent {
+            data,
+            filename,
+            content_type,
+        })
+    }
+}
+
+pub(crate) struct FileContent {
+    pub(crate) data: Vec<u8>,
+    pub(crate) filename: String,
+    pub(crate) content_type: String,
+}
+
+impl FileContent {
+    /// Encode the content as a base64 **data:** URL.
+    pub(crate) fn to_data_url(&self) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.content_type,
+            STANDARD.encode(&self.data)
+        )
+    }
+}
+
+/// How file inputs are sent along with a prediction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileEncoding {
+    /// Send files up to [`MAX_DATA_URL_SIZE`] as data URLs and upload larger ones.
+    #[default]
+    Auto,
+
+    /// Embed files in the request as base64 data URLs.
+    DataUrl,
+
+    /// Upload files with the files API and pass their URL.
+    Upload,
+}
+
+/// Set each `(name, value)` pair as a field of the `input` object.
+pub(crate) fn insert_inputs(
+    input: &mut serde_json::Value,
+    values: Vec<(String, String)>,
+) -> Result<(), Error> {
+    if values.is_empty() {
+        return Ok(());
+    }
+    if input.is_null() {
+        *input = serde_json::Value::Object(Default::default());
+    }
+    let object = input
+        .as_object_mut()
+        .ok_or_else(|| Error::InvalidFileInput("input must be a JSON object".to_string()))?;
+    for (name, value) in values {
+        object.insert(name, serde_json::Value::String(value));
+    }
+    Ok(())
+}
+
+/// Collect the URLs found in a prediction output, whether it is a single URL or a list of them.
+pub(crate) fn collect_urls<'a>(value: &'a serde_json::Value, urls: &mut Vec<&'a str>) {
+    match value {
+        serde_json::Value::String(value)
+            if value.starts_with("https://") || value.starts_with("http://") =>
+        {
+            urls.push(value)
+        }
+        serde_json::Value::Array(values) => {
+            values.iter().for_each(|value| collect_urls(value, urls))
+        }
+        serde_json::Value::Object(values) => {
+            values.values().for_each(|value| collect_urls(value, urls))
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn should_encode_bytes_as_data_url() {
+        let content = FileInput::bytes(b"hello".to_vec(), "hello.txt")
+            .read()
+            .await
+            .unwrap();
+        assert_eq!(content.content_type, "text/plain");
+        assert_eq!(content.to_data_url(), "data:text/plain;base64,aGVsbG8=");
+    }
+
+    #[tokio::test]
+    async fn should_not_read_url_input() {
+        let result = FileInput::url("https://example.com/image.png").read().await;
+        assert!(matches!(result, Err(Error::InvalidFileInput(_))));
+    }
+
+    #[test]
+    fn should_insert_inputs() {
+        let mut input = serde_json::json!({ "prompt": "A cat" });
+        insert_inputs(
+            &mut input,
+            vec![(
+                "image".to_string(),
+                "data:image/png;base64,AA==".to_string(),
+            )],
+        )
+        .unwrap();
+        assert_eq!(
+            input,
+            serde_json::json!({ "prompt": "A cat", "image": "data:image/png;base64,AA==" })
+        );
+
+        let mut input = serde_json::json!(["not", "an", "object"]);
+        assert!(insert_inputs(&mut input, vec![("image".to_string(), "".to_string())]).is_err());
+    }
+
+    #[test]
+    fn should_collect_output_urls() {
+        let output = serde_json::json!([
+            "https://replicate.delivery/pbxt/a.webp",
+            { "audio": "https://replicate.delivery/pbxt/b.wav", "text": "done" },
+            "not a url"
+        ]);
+        let mut urls = Vec::new();
+        collect_urls(&output, &mut urls);
+        assert_eq!(
+            urls,
+            vec![
+                "https://replicate.delivery/pbxt/a.webp",
+                "https://replicate.delivery/pbxt/b.wav"
+            ]
+        );
+    }
+}
@@ -3,3 +3,11 @@
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod files;
+pub mod pagination;
+pub mod reference;
+pub mod schema;
+pub mod stream;
+pub mod timestamp;
+pub mod wait;
+pub mod webhooks;
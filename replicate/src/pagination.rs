@@ -0,0 +1,112 @@
+use futures_util::{stream, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
+use std::future::Future;
+
+use crate::error::Error;
+
+/// A page of results returned by a list endpoint.
+pub trait Page: DeserializeOwned {
+    type Item;
+
+    /// URL of the next page, if any.
+    fn next(&self) -> Option<&str>;
+
+    /// Consume the page and return its results.
+    fn into_results(self) -> Vec<Self::Item>;
+}
+
+macro_rules! impl_page {
+    ($($page:ty => $item:ty),* $(,)?) => {
+        $(
+            impl $crate::pagination::Page for $page {
+                type Item = $item;
+
+                fn next(&self) -> Option<&str> {
+                    self.next.as_deref()
+                }
+
+                fn into_results(self) -> Vec<Self::Item> {
+                    self.results
+                }
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_page;
+
+/// Fetch `first` then every page linked by [`Page::next`], yielding their results one by one.
+///
+/// Pages are only fetched once the results of the previous page have been consumed.
+pub(crate) fn paginate<P, F, Fut>(
+    first: String,
+    mut fetch: F,
+) -> impl Stream<Item = Result<P::Item, Error>>
+where
+    P: Page,
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<P, Error>>,
+{
+    stream::try_unfold(Some(first), move |url| {
+        let page = url.map(&mut fetch);
+        async move {
+            let Some(page) = page else {
+                return Ok::<_, Error>(None);
+            };
+            let page = page.await?;
+            let next = page.next().map(str::to_string);
+            let results = stream::iter(page.into_results().into_iter().map(Ok));
+            Ok(Some((results, next)))
+        }
+    })
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Numbers {
+        next: Option<String>,
+        results: Vec<u32>,
+    }
+
+    impl_page!(Numbers => u32);
+
+    fn page(url: &str) -> Result<Numbers, Error> {
+        match url {
+            "numbers" => Ok(Numbers {
+                next: Some("numbers?cursor=2".to_string()),
+                results: vec![1, 2],
+            }),
+            "numbers?cursor=2" => Ok(Numbers {
+                next: None,
+                results: vec![3],
+            }),
+            url => Err(Error::NotFound(url.to_string())),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_follow_next_pages() {
+        let items: Vec<u32> = paginate("numbers".to_string(), |url| async move { page(&url) })
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn should_stop_on_error() {
+        let items: Vec<Result<u32, Error>> =
+            paginate("missing".to_string(), |url| async move { page(&url) })
+                .collect()
+                .await;
+        assert_eq!(items.len(), 1);
+        assert!(matches!(items[0], Err(Error::NotFound(_))));
+    }
+}
@@ -0,0 +1,117 @@
+use core::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Reference to a model or deployment written as **owner/name** or **owner/name:version**.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelRef {
+    /// The user or organization owning the model.
+    pub owner: String,
+
+    /// The name of the model.
+    pub name: String,
+
+    /// The 64-character string ID of a model version. The latest version is used when unset.
+    pub version: Option<String>,
+}
+
+impl ModelRef {
+    pub fn new(owner: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            name: name.into(),
+            version: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+}
+
+impl FromStr for ModelRef {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (model, version) = match s.split_once(':') {
+            Some((model, version)) => (model, Some(version)),
+            None => (s, None),
+        };
+        let (owner, name) = model
+            .split_once('/')
+            .ok_or_else(|| Error::InvalidModelRef(s.to_string()))?;
+
+        let is_valid = |part: &str| !part.is_empty() && !part.contains(['/', ':']);
+        if !is_valid(owner) || !is_valid(name) || version.is_some_and(|version| !is_valid(version))
+        {
+            return Err(Error::InvalidModelRef(s.to_string()));
+        }
+
+        Ok(Self {
+            owner: owner.to_string(),
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        })
+    }
+}
+
+impl fmt::Display for ModelRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, ":{}", version)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_model_ref() {
+        assert_eq!(
+            "black-forest-labs/flux-schnell"
+                .parse::<ModelRef>()
+                .unwrap(),
+            ModelRef::new("black-forest-labs", "flux-schnell")
+        );
+
+        let model = "replicate/hello-world:5c7d5dc6dd8bf75c1acaa8565735e7986bc5b66206b55cca93cb72c9bf15ccaa"
+            .parse::<ModelRef>()
+            .unwrap();
+        assert_eq!(
+            model.version.as_deref(),
+            Some("5c7d5dc6dd8bf75c1acaa8565735e7986bc5b66206b55cca93cb72c9bf15ccaa")
+        );
+        assert_eq!(
+            model.to_string(),
+            "replicate/hello-world:5c7d5dc6dd8bf75c1acaa8565735e7986bc5b66206b55cca93cb72c9bf15ccaa"
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_model_ref() {
+        for reference in [
+            "flux-schnell",
+            "/flux",
+            "owner/",
+            "a/b/c",
+            "a/b:",
+            "a/b:c:d",
+        ] {
+            assert!(
+                matches!(
+                    reference.parse::<ModelRef>(),
+                    Err(Error::InvalidModelRef(_))
+                ),
+                "{} should be invalid",
+                reference
+            );
+        }
+    }
+}
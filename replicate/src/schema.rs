@@ -0,0 +1,305 @@
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::error::Error;
+
+const SCHEMAS_POINTER: &str = "/components/schemas";
+const REF_PREFIX: &str = "#/components/schemas/";
+
+/// Schema of the input of a model version, extracted from its OpenAPI definition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputSchema {
+    /// Input fields by name.
+    #[serde(default)]
+    pub properties: BTreeMap<String, PropertySchema>,
+
+    /// Names of the fields without default value.
+    #[serde(default)]
+    pub required: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropertySchema {
+    /// JSON type of the field e.g. **string** or **integer**.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+
+    pub title: Option<String>,
+
+    pub description: Option<String>,
+
+    /// Value used when the field is not set.
+    pub default: Option<Value>,
+
+    /// Format of string fields e.g. **uri** for files.
+    pub format: Option<String>,
+
+    /// Inclusive lower bound of numeric fields.
+    pub minimum: Option<f64>,
+
+    /// Inclusive upper bound of numeric fields.
+    pub maximum: Option<f64>,
+
+    /// Values accepted by the field, when restricted.
+    #[serde(rename = "enum")]
+    pub allowed_values: Option<Vec<Value>>,
+
+    /// Position of the field in the model's form.
+    #[serde(rename = "x-order")]
+    pub order: Option<u32>,
+
+    /// References to shared schemas, used by Cog for fields restricted to a set of values.
+    #[serde(rename = "allOf", default, skip_serializing)]
+    all_of: Vec<Value>,
+}
+
+impl InputSchema {
+    /// Extract the **Input** schema of an OpenAPI definition, resolving references to shared schemas.
+    pub fn from_openapi(openapi: &Value) -> Option<Self> {
+        let schemas = openapi.pointer(SCHEMAS_POINTER)?;
+        let mut schema = Self::deserialize(schemas.get("Input")?).ok()?;
+        for property in schema.properties.values_mut() {
+            let referenced = property
+                .all_of
+                .iter()
+                .filter_map(|item| item.get("$ref")?.as_str()?.strip_prefix(REF_PREFIX))
+                .filter_map(|name| PropertySchema::deserialize(schemas.get(name)?).ok());
+            for referenced in referenced {
+                property.kind = property.kind.take().or(referenced.kind);
+                property.allowed_values =
+                    property.allowed_values.take().or(referenced.allowed_values);
+            }
+        }
+        Some(schema)
+    }
+
+    /// Check `input` against the schema, reporting every invalid field.
+    ///
+    /// Fields unknown to the schema are ignored.
+    pub fn validate(&self, input: &Value) -> Result<(), Error> {
+        let Some(input) = input.as_object() else {
+            return Err(Error::InvalidInput(vec![InputError {
+                field: "input".to_string(),
+                reason: InputErrorReason::InvalidType {
+                    expected: "object".to_string(),
+                },
+            }]));
+        };
+
+        let mut errors = Vec::new();
+        for field in &self.required {
+            if input.get(field).is_none_or(Value::is_null) {
+                errors.push(InputError {
+                    field: field.clone(),
+                    reason: InputErrorReason::Missing,
+                });
+            }
+        }
+        for (field, value) in input {
+            if let Some(reason) = self
+                .properties
+                .get(field)
+                .and_then(|property| property.check(value))
+            {
+                errors.push(InputError {
+                    field: field.clone(),
+                    reason,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidInput(errors))
+        }
+    }
+}
+
+impl PropertySchema {
+    fn check(&self, value: &Value) -> Option<InputErrorReason> {
+        if value.is_null() {
+            return None;
+        }
+        if let Some(kind) = &self.kind {
+            let matches = match kind.as_str() {
+                "string" => value.is_string(),
+                "integer" => value.is_i64() || value.is_u64(),
+                "number" => value.is_number(),
+                "boolean" => value.is_boolean(),
+                "array" => value.is_array(),
+                "object" => value.is_object(),
+                _ => true,
+            };
+            if !matches {
+                return Some(InputErrorReason::InvalidType {
+                    expected: kind.clone(),
+                });
+            }
+        }
+        if let Some(allowed) = &self.allowed_values {
+            if !allowed.contains(value) {
+                return Some(InputErrorReason::NotAllowed {
+                    allowed: allowed.clone(),
+                });
+            }
+        }
+        if let Some(number) = value.as_f64() {
+            let too_small = self.minimum.is_some_and(|minimum| number < minimum);
+            let too_large = self.maximum.is_some_and(|maximum| number > maximum);
+            if too_small || too_large {
+                return Some(InputErrorReason::OutOfRange {
+                    minimum: self.minimum,
+                    maximum: self.maximum,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// An input field rejected by [`InputSchema::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputError {
+    /// Name of the field.
+    pub field: String,
+
+    pub reason: InputErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputErrorReason {
+    /// A required field is not set.
+    Missing,
+
+    /// The value doesn't have the expected JSON type.
+    InvalidType { expected: String },
+
+    /// The value is not one of the values accepted by the field.
+    NotAllowed { allowed: Vec<Value> },
+
+    /// The number is outside of the accepted bounds.
+    OutOfRange {
+        minimum: Option<f64>,
+        maximum: Option<f64>,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.field)?;
+        match &self.reason {
+            InputErrorReason::Missing => write!(f, "missing required field"),
+            InputErrorReason::InvalidType { expected } => write!(f, "expected {}", expected),
+            InputErrorReason::NotAllowed { allowed } => {
+                let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+                write!(f, "must be one of {}", allowed.join(", "))
+            }
+            InputErrorReason::OutOfRange { minimum, maximum } => match (minimum, maximum) {
+                (Some(minimum), Some(maximum)) => {
+                    write!(f, "must be between {} and {}", minimum, maximum)
+                }
+                (Some(minimum), None) => write!(f, "must be at least {}", minimum),
+                (None, Some(maximum)) => write!(f, "must be at most {}", maximum),
+                (None, None) => write!(f, "out of range"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn openapi() -> Value {
+        serde_json::json!({
+            "components": {
+                "schemas": {
+                    "Input": {
+                        "type": "object",
+                        "title": "Input",
+                        "required": ["prompt"],
+                        "properties": {
+                            "prompt": { "type": "string", "title": "Prompt", "x-order": 0 },
+                            "num_outputs": {
+                                "type": "integer",
+                                "default": 1,
+                                "minimum": 1,
+                                "maximum": 4,
+                                "x-order": 1
+                            },
+                            "aspect_ratio": {
+                                "allOf": [{ "$ref": "#/components/schemas/aspect_ratio" }],
+                                "default": "1:1",
+                                "x-order": 2
+                            },
+                            "image": { "type": "string", "format": "uri", "x-order": 3 }
+                        }
+                    },
+                    "aspect_ratio": {
+                        "type": "string",
+                        "enum": ["1:1", "16:9"],
+                        "title": "aspect_ratio"
+                    },
+                    "Output": {
+                        "type": "array",
+                        "items": { "type": "string", "format": "uri" }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn should_resolve_referenced_schemas() {
+        let schema = InputSchema::from_openapi(&openapi()).unwrap();
+        let aspect_ratio = &schema.properties["aspect_ratio"];
+        assert_eq!(aspect_ratio.kind.as_deref(), Some("string"));
+        assert_eq!(
+            aspect_ratio.allowed_values,
+            Some(vec![serde_json::json!("1:1"), serde_json::json!("16:9")])
+        );
+        assert_eq!(schema.required, vec!["prompt".to_string()]);
+    }
+
+    #[test]
+    fn should_accept_valid_input() {
+        let schema = InputSchema::from_openapi(&openapi()).unwrap();
+        let input = serde_json::json!({
+            "prompt": "A baby dragon",
+            "num_outputs": 2,
+            "aspect_ratio": "16:9",
+            "unknown": true
+        });
+        assert!(schema.validate(&input).is_ok());
+    }
+
+    #[test]
+    fn should_report_invalid_fields() {
+        let schema = InputSchema::from_openapi(&openapi()).unwrap();
+        let input = serde_json::json!({
+            "num_outputs": 10,
+            "aspect_ratio": "4:3",
+            "image": 42
+        });
+
+        match schema.validate(&input) {
+            Err(Error::InvalidInput(errors)) => {
+                let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                assert_eq!(
+                    messages,
+                    vec![
+                        "prompt: missing required field",
+                        "aspect_ratio: must be one of \"1:1\", \"16:9\"",
+                        "image: expected string",
+                        "num_outputs: must be between 1 and 4",
+                    ]
+                );
+            }
+            other => panic!("Expected 'InvalidInput' error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// Event sent by the stream of a prediction created with **stream = true**.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PredictionEvent {
+    /// A chunk of output generated by the model.
+    Output(String),
+
+    /// Log lines printed by the model.
+    Logs(String),
+
+    /// The prediction failed.
+    Error(StreamError),
+
+    /// The prediction reached a terminal status. This is always the last event.
+    Done(StreamDone),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamError {
+    /// Human-readable explanation of the error.
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamDone {
+    /// Why the prediction stopped e.g. **canceled** or **error**. Unset when the prediction succeeded.
+    pub reason: Option<String>,
+}
+
+impl PredictionEvent {
+    fn parse(event: &str, data: String) -> Result<Option<Self>, Error> {
+        let event = match event {
+            "output" => Self::Output(data),
+            "logs" => Self::Logs(data),
+            "error" => {
+                Self::Error(serde_json::from_str(&data).unwrap_or(StreamError { detail: data }))
+            }
+            "done" if data.trim().is_empty() => Self::Done(StreamDone::default()),
+            "done" => Self::Done(serde_json::from_str(&data)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+/// Incremental parser turning raw server-sent events bytes into prediction events.
+///
+/// Events can be split across several network chunks so incomplete events are buffered until their terminating blank line is received. The **\r\n** and **\r** line endings are normalized to **\n**.
+#[derive(Debug, Default)]
+pub(crate) struct EventParser {
+    buffer: Vec<u8>,
+
+    /// Whether the last byte received is a **\r**, so that a following **\n** belongs to the same line ending.
+    after_cr: bool,
+}
+
+impl EventParser {
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<Result<PredictionEvent, Error>> {
+        for &byte in bytes {
+            match byte {
+                b'\r' => {
+                    self.buffer.push(b'\n');
+                    self.after_cr = true;
+                }
+                b'\n' if self.after_cr => self.after_cr = false,
+                _ => {
+                    self.buffer.push(byte);
+                    self.after_cr = false;
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        while let Some(position) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+            let event: Vec<u8> = self.buffer.drain(..position + 2).collect();
+            events.extend(
+                Self::parse_event(&String::from_utf8_lossy(&event[..position])).transpose(),
+            );
+        }
+        events
+    }
+
+    /// Parse the last event when the stream ends without a blank line.
+    pub(crate) fn finish(&mut self) -> Vec<Result<PredictionEvent, Error>> {
+        let event = std::mem::take(&mut self.buffer);
+        Self::parse_event(&String::from_utf8_lossy(&event))
+            .transpose()
+            .into_iter()
+            .collect()
+    }
+
+    fn parse_event(raw: &str) -> Result<Option<PredictionEvent>, Error> {
+        let mut event = "message";
+        let mut data: Vec<&str> = Vec::new();
+        for line in raw.lines() {
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => event = value,
+                "data" => data.push(value),
+                _ => {}
+            }
+        }
+        PredictionEvent::parse(event, data.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_parse_events() {
+        let mut parser = EventParser::default();
+        let raw = "event: output\nid: 1690212292:0\ndata: Once upon\n\nevent: output\nid: 1690212292:1\ndata:  a time\n\nevent: logs\ndata: loading weights\ndata: done\n\nevent: done\ndata: {}\n\n";
+
+        let events: Vec<PredictionEvent> = parser
+            .push(raw.as_bytes())
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                PredictionEvent::Output("Once upon".to_string()),
+                PredictionEvent::Output(" a time".to_string()),
+                PredictionEvent::Logs("loading weights\ndone".to_string()),
+                PredictionEvent::Done(StreamDone { reason: None }),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_buffer_events_split_across_chunks() {
+        let mut parser = EventParser::default();
+        assert!(parser.push(b"event: output\r\ndata: Hel").is_empty());
+
+        let events = parser.push(b"lo\r\n\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_ref().unwrap(),
+            &PredictionEvent::Output("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn should_parse_trailing_event_on_finish() {
+        let mut parser = EventParser::default();
+        assert!(parser.push(b"event: done\ndata: {}").is_empty());
+
+        let events = parser.finish();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_ref().unwrap(),
+            &PredictionEvent::Done(StreamDone { reason: None })
+        );
+        assert!(parser.finish().is_empty());
+    }
+
+    #[test]
+    fn should_normalize_line_endings() {
+        let mut parser = EventParser::default();
+        assert!(parser.push(b"event: output\rdata: Hello\r").is_empty());
+
+        let events = parser.push(b"\n\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_ref().unwrap(),
+            &PredictionEvent::Output("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn should_parse_error_and_canceled_events() {
+        let mut parser = EventParser::default();
+        let raw = "event: error\ndata: {\"detail\": \"CUDA out of memory\"}\n\nevent: done\ndata: {\"reason\": \"error\"}\n\n: keep-alive\n\n";
+
+        let events: Vec<PredictionEvent> = parser
+            .push(raw.as_bytes())
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                PredictionEvent::Error(StreamError {
+                    detail: "CUDA out of memory".to_string()
+                }),
+                PredictionEvent::Done(StreamDone {
+                    reason: Some("error".to_string())
+                }),
+            ]
+        );
+    }
+}
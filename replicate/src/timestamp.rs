@@ -0,0 +1,43 @@
+//! Timestamps returned by the API.
+//!
+//! They are kept as RFC 3339 strings. With the **chrono** feature enabled, the resources have accessors parsing them into [`chrono::DateTime`].
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "chrono")]
+use std::time::Duration;
+
+/// A point in time returned by the API e.g. **2023-09-08T16:19:34.765994657Z**.
+pub type Timestamp = String;
+
+/// Parse a timestamp, if well formed.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse(timestamp: &str) -> Option<DateTime<Utc>> {
+    timestamp.parse().ok()
+}
+
+/// Time elapsed between two optional timestamps, if both are known, well formed and in order.
+#[cfg(feature = "chrono")]
+pub(crate) fn elapsed(from: Option<&str>, to: Option<&str>) -> Option<Duration> {
+    (parse(to?)? - parse(from?)?).to_std().ok()
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_compute_elapsed_time() {
+        let from = "2023-09-08T16:19:34.5Z";
+        let to = "2023-09-08T16:19:36Z";
+
+        assert_eq!(
+            elapsed(Some(from), Some(to)),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(elapsed(Some(to), Some(from)), None);
+        assert_eq!(elapsed(Some(from), None), None);
+        assert_eq!(elapsed(Some("yesterday"), Some(to)), None);
+    }
+}
@@ -0,0 +1,213 @@
+use std::{future::Future, time::Duration};
+use tokio::time::Instant;
+
+use crate::error::Error;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(5);
+const DEFAULT_BACKOFF: f64 = 1.5;
+const MAX_BACKOFF: f64 = 10.0;
+const MAX_PREFER_WAIT: u8 = 60;
+
+/// Options controlling how long and how often to poll, e.g. while waiting for a prediction to complete.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaitOptions {
+    /// Number of seconds, between 1 and 60, the API holds the creation request open with the **Prefer: wait** header.
+    ///
+    /// Predictions completing within that time are returned without polling.
+    pub prefer_wait: Option<u8>,
+
+    /// Delay before the first poll.
+    ///
+    /// Defaults to **500ms**.
+    pub interval: Duration,
+
+    /// Upper bound of the delay between two polls.
+    ///
+    /// Defaults to **5s**.
+    pub max_interval: Duration,
+
+    /// Factor applied to the delay after each poll.
+    ///
+    /// Defaults to **1.5**. A factor of **1.0** polls at a fixed interval. Values are clamped between **1.0** and **10.0** when polling, NaN falls back to the default.
+    pub backoff: f64,
+
+    /// Maximum time to wait before giving up with [`Error::Timeout`].
+    ///
+    /// Waits indefinitely when unset.
+    pub timeout: Option<Duration>,
+}
+
+impl WaitOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_prefer_wait(mut self, seconds: u8) -> Self {
+        self.prefer_wait = Some(seconds.clamp(1, MAX_PREFER_WAIT));
+        self
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    pub fn with_backoff(mut self, backoff: f64) -> Self {
+        self.backoff = clamp_backoff(backoff);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn next_interval(&self, interval: Duration) -> Duration {
+        let seconds = interval.as_secs_f64() * clamp_backoff(self.backoff);
+        Duration::try_from_secs_f64(seconds)
+            .unwrap_or(self.max_interval)
+            .min(self.max_interval)
+    }
+
+    /// Call `fetch` until `is_done` returns true for the fetched value, sleeping between calls according to the options.
+    pub(crate) async fn poll<T, F, Fut>(
+        &self,
+        mut current: T,
+        mut fetch: F,
+        is_done: impl Fn(&T) -> bool,
+    ) -> Result<T, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let started_at = Instant::now();
+        let mut interval = self.interval;
+        while !is_done(&current) {
+            let delay = match self.timeout {
+                Some(timeout) => {
+                    let elapsed = started_at.elapsed();
+                    if elapsed >= timeout {
+                        return Err(Error::Timeout(timeout));
+                    }
+                    interval.min(timeout - elapsed)
+                }
+                None => interval,
+            };
+            tokio::time::sleep(delay).await;
+            current = fetch().await?;
+            interval = self.next_interval(interval);
+        }
+        Ok(current)
+    }
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            prefer_wait: None,
+            interval: DEFAULT_INTERVAL,
+            max_interval: DEFAULT_MAX_INTERVAL,
+            backoff: DEFAULT_BACKOFF,
+            timeout: None,
+        }
+    }
+}
+
+fn clamp_backoff(backoff: f64) -> f64 {
+    if backoff.is_nan() {
+        return DEFAULT_BACKOFF;
+    }
+    backoff.clamp(1.0, MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn should_increase_interval_up_to_max() {
+        let options = WaitOptions::new()
+            .with_interval(Duration::from_secs(1))
+            .with_max_interval(Duration::from_secs(2))
+            .with_backoff(1.5);
+
+        let interval = options.next_interval(options.interval);
+        assert_eq!(interval, Duration::from_millis(1500));
+
+        let interval = options.next_interval(interval);
+        assert_eq!(interval, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn should_clamp_options() {
+        let options = WaitOptions::new().with_prefer_wait(120).with_backoff(0.5);
+        assert_eq!(options.prefer_wait, Some(60));
+        assert_eq!(options.backoff, 1.0);
+
+        let options = WaitOptions::new().with_backoff(f64::INFINITY);
+        assert_eq!(options.backoff, MAX_BACKOFF);
+
+        let options = WaitOptions::new().with_backoff(f64::NAN);
+        assert_eq!(options.backoff, DEFAULT_BACKOFF);
+    }
+
+    #[test]
+    fn should_clamp_backoff_set_directly() {
+        let mut options = WaitOptions::new()
+            .with_interval(Duration::from_secs(1))
+            .with_max_interval(Duration::MAX);
+
+        options.backoff = -2.0;
+        assert_eq!(options.next_interval(options.interval), options.interval);
+
+        options.backoff = f64::INFINITY;
+        assert_eq!(
+            options.next_interval(options.interval),
+            Duration::from_secs(10)
+        );
+
+        options.backoff = f64::NAN;
+        assert_eq!(
+            options.next_interval(options.interval),
+            Duration::from_millis(1500)
+        );
+
+        let interval = options.next_interval(Duration::MAX);
+        assert_eq!(interval, Duration::MAX);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_poll_until_done() {
+        let options = WaitOptions::new();
+        let mut calls = 0;
+        let result = options
+            .poll(
+                0,
+                || {
+                    calls += 1;
+                    let value = calls;
+                    async move { Ok(value) }
+                },
+                |value| *value == 3,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn should_time_out() {
+        let options = WaitOptions::new().with_timeout(Duration::from_secs(3));
+        let result = options
+            .poll(0, || async { Ok(0) }, |value| *value == 1)
+            .await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+    }
+}
@@ -0,0 +1,209 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::client::{Prediction, WebHookSecret};
+
+pub const WEBHOOK_ID_HEADER: &str = "webhook-id";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "webhook-timestamp";
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "webhook-signature";
+
+const SECRET_PREFIX: &str = "whsec_";
+const SIGNATURE_VERSION: &str = "v1";
+const DEFAULT_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error("Missing webhook header: {0}")]
+    MissingHeader(&'static str),
+
+    #[error("Invalid webhook secret")]
+    InvalidSecret,
+
+    #[error("Invalid webhook timestamp")]
+    InvalidTimestamp,
+
+    #[error("Webhook timestamp is outside of the tolerance window")]
+    TimestampOutOfRange,
+
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+
+    #[error("Invalid webhook payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+}
+
+/// Verify that incoming webhooks were sent by Replicate.
+///
+/// Webhooks are signed with the secret returned by [`Client::webhook_default_secret`](crate::client::Client::webhook_default_secret).
+/// Requests older than the tolerance window, 5 minutes by default, are rejected to prevent replay attacks.
+#[derive(Debug, Clone)]
+pub struct WebhookVerifier {
+    key: Vec<u8>,
+    tolerance: Duration,
+}
+
+impl WebhookVerifier {
+    /// Create a verifier from a signing secret e.g. **whsec_C2FVsBQIhrscChlQIMV+b5sSYspob7oD**.
+    pub fn new(secret: &str) -> Result<Self, WebhookError> {
+        let secret = secret.strip_prefix(SECRET_PREFIX).unwrap_or(secret);
+        let key = STANDARD
+            .decode(secret)
+            .map_err(|_| WebhookError::InvalidSecret)?;
+        Ok(Self {
+            key,
+            tolerance: DEFAULT_TOLERANCE,
+        })
+    }
+
+    /// Maximum age, or clock drift, accepted for the **webhook-timestamp** header.
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Verify the signature headers against the raw request body.
+    pub fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), WebhookError> {
+        self.verify_at(headers, body, SystemTime::now())
+    }
+
+    /// Verify the webhook and parse its body into the prediction it describes.
+    pub fn verify_prediction(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Prediction, WebhookError> {
+        self.verify(headers, body)?;
+        Ok(serde_json::from_slice::<Prediction>(body)?)
+    }
+
+    fn verify_at(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+        now: SystemTime,
+    ) -> Result<(), WebhookError> {
+        let id = header(headers, WEBHOOK_ID_HEADER)?;
+        let timestamp = header(headers, WEBHOOK_TIMESTAMP_HEADER)?;
+        let signatures = header(headers, WEBHOOK_SIGNATURE_HEADER)?;
+
+        let sent_at = timestamp
+            .parse::<u64>()
+            .map_err(|_| WebhookError::InvalidTimestamp)?;
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| WebhookError::InvalidTimestamp)?
+            .as_secs();
+        if now.abs_diff(sent_at) > self.tolerance.as_secs() {
+            return Err(WebhookError::TimestampOutOfRange);
+        }
+
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.key).map_err(|_| WebhookError::InvalidSecret)?;
+        mac.update(format!("{}.{}.", id, timestamp).as_bytes());
+        mac.update(body);
+
+        let verified = signatures
+            .split_whitespace()
+            .filter_map(|signature| signature.split_once(','))
+            .filter(|(version, _)| *version == SIGNATURE_VERSION)
+            .filter_map(|(_, signature)| STANDARD.decode(signature).ok())
+            .any(|signature| mac.clone().verify_slice(&signature).is_ok());
+        if verified {
+            Ok(())
+        } else {
+            Err(WebhookError::InvalidSignature)
+        }
+    }
+}
+
+impl TryFrom<&WebHookSecret> for WebhookVerifier {
+    type Error = WebhookError;
+
+    fn try_from(secret: &WebHookSecret) -> Result<Self, Self::Error> {
+        Self::new(&secret.key)
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, WebhookError> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or(WebhookError::MissingHeader(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderValue;
+
+    const SECRET: &str = "whsec_MfKQ9r8GKYqrTwjUPD8ILPZIo2LaLaSw";
+    const ID: &str = "msg_p5jXN8AQM9LWM0D4loKWxJek";
+    const TIMESTAMP: u64 = 1614265330;
+    const BODY: &str = r#"{"test": 2432232314}"#;
+    const SIGNATURE: &str = "v1,g0hM9SsE+OTPJTGt/tmIKtSyZlE3uFJELVlNIOLJ1OE=";
+
+    fn headers(signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(WEBHOOK_ID_HEADER, HeaderValue::from_static(ID));
+        headers.insert(
+            WEBHOOK_TIMESTAMP_HEADER,
+            HeaderValue::from_str(&TIMESTAMP.to_string()).unwrap(),
+        );
+        headers.insert(
+            WEBHOOK_SIGNATURE_HEADER,
+            HeaderValue::from_str(signature).unwrap(),
+        );
+        headers
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn should_verify_valid_signature() {
+        let verifier = WebhookVerifier::new(SECRET).unwrap();
+        let headers = headers(&format!("v1,invalid {}", SIGNATURE));
+        assert!(verifier
+            .verify_at(&headers, BODY.as_bytes(), at(TIMESTAMP + 10))
+            .is_ok());
+    }
+
+    #[test]
+    fn should_reject_tampered_body() {
+        let verifier = WebhookVerifier::new(SECRET).unwrap();
+        let result = verifier.verify_at(&headers(SIGNATURE), br#"{"test": 1}"#, at(TIMESTAMP));
+        assert!(matches!(result, Err(WebhookError::InvalidSignature)));
+    }
+
+    #[test]
+    fn should_reject_replayed_webhook() {
+        let verifier = WebhookVerifier::new(SECRET)
+            .unwrap()
+            .with_tolerance(Duration::from_secs(60));
+        let result = verifier.verify_at(&headers(SIGNATURE), BODY.as_bytes(), at(TIMESTAMP + 61));
+        assert!(matches!(result, Err(WebhookError::TimestampOutOfRange)));
+    }
+
+    #[test]
+    fn should_reject_missing_headers() {
+        let verifier = WebhookVerifier::new(SECRET).unwrap();
+        let result = verifier.verify_at(&HeaderMap::new(), BODY.as_bytes(), at(TIMESTAMP));
+        match result {
+            Err(WebhookError::MissingHeader(header)) => assert_eq!(header, WEBHOOK_ID_HEADER),
+            other => panic!("Expected 'MissingHeader' error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_secret() {
+        assert!(matches!(
+            WebhookVerifier::new("whsec_not base64!"),
+            Err(WebhookError::InvalidSecret)
+        ));
+    }
+}